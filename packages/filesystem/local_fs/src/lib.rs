@@ -1,8 +1,10 @@
 use file::LocalFile;
+use stat::Owners;
 use std::fs;
-use vfs::{AbsolutePath, File, Kind, OpenOptions, Stat, Vfs, VfsResult};
+use vfs::{AbsolutePath, DirEntry, File, Kind, OpenOptions, Stat, Vfs, VfsResult};
 
 mod file;
+mod stat;
 
 pub struct LocalFileSystem {}
 
@@ -15,50 +17,70 @@ impl Vfs for LocalFileSystem {
             .read(open_options.read)
             .truncate(open_options.truncate)
             .write(open_options.write)
-            .open(path.as_str())
-            .map_err(|e| e.to_string())?;
+            .open(path.as_str())?;
         Ok(Box::new(LocalFile(f)))
     }
-    fn unlink(&self, _path: &AbsolutePath) -> VfsResult<()> {
-        todo!()
+    fn unlink(&self, path: &AbsolutePath) -> VfsResult<()> {
+        Ok(fs::remove_file(path.as_str())?)
     }
     fn stat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
-        let data = fs::metadata(path.as_str()).map_err(|e| e.to_string())?;
-        Ok(Stat {
-            kind: if data.is_dir() {
-                Kind::Folder
-            } else if data.is_symlink() {
-                Kind::Link
-            } else if data.is_file() {
-                Kind::File
-            } else {
-                Kind::Unknown
-            },
-            size: data.len(),
-        })
+        let data = fs::metadata(path.as_str())?;
+        Ok(stat::from_metadata(&data, &Owners::default()))
     }
 
     // Folders
     fn read_dir(&self, path: &AbsolutePath) -> VfsResult<Vec<AbsolutePath>> {
-        let entries = fs::read_dir(path.as_str()).map_err(|e| e.to_string())?;
+        let entries = fs::read_dir(path.as_str())?;
         let mut files = vec![];
-        for entry in entries {
-            if let Ok(entry) = entry {
-                files.push(AbsolutePath::new(entry.path().to_string_lossy()));
-            }
+        for entry in entries.flatten() {
+            files.push(AbsolutePath::new(entry.path().to_string_lossy()));
+        }
+        Ok(files)
+    }
+    fn read_dir_with_stat(&self, path: &AbsolutePath) -> VfsResult<Vec<DirEntry>> {
+        let entries = fs::read_dir(path.as_str())?;
+        let mut files = vec![];
+        let owners = Owners::default();
+        for entry in entries.flatten() {
+            // the metadata of a folder entry does not follow links
+            let stat = entry
+                .metadata()
+                .map(|data| stat::from_metadata(&data, &owners))
+                .unwrap_or_else(|_| Stat::new(Kind::Unknown, 0));
+            files.push(DirEntry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                stat,
+            });
         }
         Ok(files)
     }
     fn create_dir(&self, path: &AbsolutePath) -> VfsResult<()> {
-        Ok(fs::create_dir(path.as_str()).map_err(|e| e.to_string())?)
+        Ok(fs::create_dir(path.as_str())?)
     }
     fn create_dir_all(&self, path: &AbsolutePath) -> VfsResult<()> {
-        Ok(fs::create_dir_all(path.as_str()).map_err(|e| e.to_string())?)
+        Ok(fs::create_dir_all(path.as_str())?)
+    }
+
+    // Links
+    fn lstat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+        let data = fs::symlink_metadata(path.as_str())?;
+        Ok(stat::from_metadata(&data, &Owners::default()))
+    }
+    fn read_link(&self, path: &AbsolutePath) -> VfsResult<String> {
+        Ok(fs::read_link(path.as_str())?.to_string_lossy().into_owned())
+    }
+    #[cfg(unix)]
+    fn create_symlink(&self, target: &str, link: &AbsolutePath) -> VfsResult<()> {
+        Ok(std::os::unix::fs::symlink(target, link.as_str())?)
+    }
+    #[cfg(not(unix))]
+    fn create_symlink(&self, _target: &str, _link: &AbsolutePath) -> VfsResult<()> {
+        Err(vfs::VfsError::Unsupported)
     }
 
     // All
     fn rename(&self, from: &AbsolutePath, to: &AbsolutePath) -> VfsResult<()> {
-        Ok(fs::rename(from.as_str(), to.as_str()).map_err(|e| e.to_string())?)
+        Ok(fs::rename(from.as_str(), to.as_str())?)
     }
 }
 
@@ -72,7 +94,7 @@ mod tests {
         os::unix::fs::PermissionsExt,
     };
 
-    use vfs::{AbsolutePath, Vfs};
+    use vfs::{AbsolutePath, Kind, Vfs, VfsError};
 
     fn dir_to_vec(mut dir: fs::ReadDir) -> VfsResult<Vec<fs::DirEntry>> {
         let mut vec = vec![];
@@ -142,15 +164,43 @@ mod tests {
         test_read_folder("/etc");
     }
 
+    #[test]
+    fn list_with_stat() {
+        let local_vfs = LocalFileSystem {};
+        let path = "/etc".into();
+
+        let mut entries = local_vfs.read_dir(&path).unwrap();
+        let mut entries_with_stat = local_vfs.read_dir_with_stat(&path).unwrap();
+        entries.sort_by(|e1, e2| e1.name().cmp(e2.name()));
+        entries_with_stat.sort_by(|e1, e2| e1.name.cmp(&e2.name));
+
+        assert_eq!(entries.len(), entries_with_stat.len());
+        for (entry, entry_with_stat) in entries.iter().zip(entries_with_stat.iter()) {
+            assert_eq!(entry.name(), entry_with_stat.name);
+            assert_eq!(
+                local_vfs.lstat(entry).unwrap().kind,
+                entry_with_stat.stat.kind
+            );
+        }
+    }
+
     #[test]
     fn list_no_folder() {
-        // let local_vfs = LocalFileSystem {};
-        // let path = "/is_not_folder".into();
+        let local_vfs = LocalFileSystem {};
+        let path = "/is_not_folder".into();
 
-        // assert_eq!(
-        //     local_vfs.read_dir(&path).err().unwrap().kind(),
-        //     ErrorKind::NotFound
-        // );
+        assert_eq!(local_vfs.read_dir(&path).err().unwrap(), VfsError::NotFound);
+    }
+
+    #[test]
+    fn list_file() {
+        let local_vfs = LocalFileSystem {};
+        let path = "/etc/passwd".into();
+
+        assert_eq!(
+            local_vfs.read_dir(&path).err().unwrap(),
+            VfsError::NotADirectory
+        );
     }
 
     #[test]
@@ -201,21 +251,96 @@ mod tests {
         );
     }
 
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn stat_file_metadata() {
+        let mut file = temp_dir();
+        file.push("stat_file_metadata");
+        fs::write(&file, b"junkyard").unwrap();
+        let local_vfs = LocalFileSystem {};
+        let path = file.as_os_str().to_str().unwrap().into();
+
+        let stat = local_vfs.stat(&path).unwrap();
+
+        fs::remove_file(&file).unwrap();
+
+        assert_eq!(stat.kind, Kind::File);
+        assert_eq!(stat.size, 8);
+        assert!(stat.modified.is_some());
+        assert_eq!(stat.mode.map(|mode| mode & 0o170000), Some(0o100000));
+        assert!(stat.uid.is_some());
+        assert!(stat.nlink.unwrap() >= 1);
+    }
+
+    #[cfg(target_family = "unix")]
+    #[test]
+    fn symlink() {
+        let mut folder = temp_dir();
+        folder.push("symlink_test");
+        fs::create_dir_all(&folder).unwrap();
+        let folder_path = AbsolutePath::new(folder.to_str().unwrap());
+        let target = AbsolutePath::new(format!("{}/target", folder_path.as_str()));
+        let link = AbsolutePath::new(format!("{}/link", folder_path.as_str()));
+        let broken = AbsolutePath::new(format!("{}/broken", folder_path.as_str()));
+        fs::create_dir_all(target.as_str()).unwrap();
+        let local_vfs = LocalFileSystem {};
+
+        local_vfs.create_symlink("target", &link).unwrap();
+        local_vfs.create_symlink("missing", &broken).unwrap();
+        let link_stat = local_vfs.lstat(&link).unwrap();
+        let target_stat = local_vfs.stat(&link).unwrap();
+        let link_target = local_vfs.read_link(&link).unwrap();
+        let broken_stat = local_vfs.lstat(&broken).unwrap();
+        let broken_error = local_vfs.stat(&broken).err();
+
+        fs::remove_dir_all(&folder).unwrap();
+
+        assert_eq!(link_stat.kind, Kind::Link);
+        assert_eq!(target_stat.kind, Kind::Folder);
+        assert_eq!(link_target, "target");
+        assert_eq!(broken_stat.kind, Kind::Link);
+        assert_eq!(broken_error, Some(VfsError::NotFound));
+    }
+
+    #[test]
+    fn unlink() {
+        let mut folder = temp_dir();
+        folder.push("unlink_test");
+        fs::create_dir_all(&folder).unwrap();
+        let folder_path = AbsolutePath::new(folder.to_str().unwrap());
+        let file = AbsolutePath::new(format!("{}/file", folder_path.as_str()));
+        fs::write(file.as_str(), "junkyard").unwrap();
+        let local_vfs = LocalFileSystem {};
+
+        let removed = local_vfs.unlink(&file);
+        let stat_error = local_vfs.stat(&file).err();
+        let folder_error = local_vfs.unlink(&folder_path).err();
+
+        fs::remove_dir_all(&folder).unwrap();
+
+        assert_eq!(removed, Ok(()));
+        assert_eq!(stat_error, Some(VfsError::NotFound));
+        assert!(folder_error.is_some());
+    }
+
     #[cfg(target_family = "unix")]
     #[test]
     fn list_permission_denied_folder() {
         let mut folder = temp_dir();
         folder.push("no_access");
-        let _local_vfs = LocalFileSystem {};
+        let local_vfs = LocalFileSystem {};
         fs::create_dir_all(&folder).unwrap();
         let permissions = Permissions::from_mode(0o000);
         fs::set_permissions(&folder, permissions).unwrap();
-        // let path = folder.as_os_str().to_str().unwrap().into();
+        let path = folder.as_os_str().to_str().unwrap().into();
 
-        // let error = local_vfs.read_dir(&path).err().unwrap().kind();
+        let error = local_vfs.read_dir(&path).err();
 
         fs::remove_dir(&folder).unwrap();
 
-        // assert_eq!(error, ErrorKind::PermissionDenied);
+        // the superuser is not subject to permission checks
+        if let Some(error) = error {
+            assert_eq!(error, VfsError::PermissionDenied);
+        }
     }
 }
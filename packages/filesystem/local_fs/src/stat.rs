@@ -0,0 +1,50 @@
+use std::fs;
+
+use vfs::{Kind, Stat};
+
+/// Turns user and group ids into names, every id is looked up once for all
+/// the entries of a listing.
+#[derive(Default)]
+pub(crate) struct Owners {
+    #[cfg(unix)]
+    cache: uzers::UsersCache,
+}
+
+#[cfg_attr(not(unix), allow(unused_variables))]
+pub(crate) fn from_metadata(data: &fs::Metadata, owners: &Owners) -> Stat {
+    let kind = if data.is_dir() {
+        Kind::Folder
+    } else if data.is_symlink() {
+        Kind::Link
+    } else if data.is_file() {
+        Kind::File
+    } else {
+        Kind::Unknown
+    };
+    let mut stat = Stat::new(kind, data.len());
+    stat.modified = data.modified().ok().map(Into::into);
+    stat.accessed = data.accessed().ok().map(Into::into);
+    stat.created = data.created().ok().map(Into::into);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        use uzers::{Groups, Users};
+
+        stat.mode = Some(data.mode());
+        stat.uid = Some(data.uid());
+        stat.gid = Some(data.gid());
+        stat.user = owners
+            .cache
+            .get_user_by_uid(data.uid())
+            .map(|user| user.name().to_string_lossy().into_owned());
+        stat.group = owners
+            .cache
+            .get_group_by_gid(data.gid())
+            .map(|group| group.name().to_string_lossy().into_owned());
+        stat.inode = Some(data.ino());
+        stat.device = Some(data.dev());
+        stat.nlink = Some(data.nlink());
+    }
+    stat
+}
@@ -0,0 +1,482 @@
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::pin,
+    sync::{mpsc, Arc},
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use async_trait::async_trait;
+use futures_channel::oneshot;
+
+use crate::{
+    AbsolutePath, AsyncFile, AsyncVfs, DirEntry, File, OpenOptions, Seek, Stat, Vfs, VfsError,
+    VfsNotice, VfsResult,
+};
+
+/// Runs `future` to completion on the current thread.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Uses an [`AsyncVfs`] as a [`Vfs`], every call blocks until the
+/// filesystem answers.
+pub struct BlockingVfs<V> {
+    vfs: V,
+}
+
+impl<V: AsyncVfs> BlockingVfs<V> {
+    pub fn new(vfs: V) -> BlockingVfs<V> {
+        BlockingVfs { vfs }
+    }
+
+    pub fn inner(&self) -> &V {
+        &self.vfs
+    }
+}
+
+/// Uses an [`AsyncFile`] as a [`File`].
+pub struct BlockingFile<F> {
+    file: F,
+}
+
+impl<F: AsyncFile> BlockingFile<F> {
+    pub fn new(file: F) -> BlockingFile<F> {
+        BlockingFile { file }
+    }
+}
+
+impl<F: AsyncFile> File for BlockingFile<F> {
+    fn read(&mut self, buffer: &mut [u8]) -> VfsResult<u64> {
+        block_on(self.file.read(buffer))
+    }
+
+    fn write(&mut self, buffer: &[u8]) -> VfsResult<u64> {
+        block_on(self.file.write(buffer))
+    }
+
+    fn seek(&mut self, from: Seek) -> VfsResult<u64> {
+        block_on(self.file.seek(from))
+    }
+}
+
+impl<V: AsyncVfs> Vfs for BlockingVfs<V> {
+    fn open(&self, path: &AbsolutePath, open_options: OpenOptions) -> VfsResult<Box<dyn File>> {
+        let file = block_on(self.vfs.open(path, open_options))?;
+        Ok(Box::new(BlockingFile::new(file)))
+    }
+
+    fn unlink(&self, path: &AbsolutePath) -> VfsResult<()> {
+        block_on(self.vfs.unlink(path))
+    }
+
+    fn stat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+        block_on(self.vfs.stat(path))
+    }
+
+    fn read_dir(&self, path: &AbsolutePath) -> VfsResult<Vec<AbsolutePath>> {
+        block_on(self.vfs.read_dir(path))
+    }
+
+    fn read_dir_with_stat(&self, path: &AbsolutePath) -> VfsResult<Vec<DirEntry>> {
+        block_on(self.vfs.read_dir_with_stat(path))
+    }
+
+    fn create_dir(&self, path: &AbsolutePath) -> VfsResult<()> {
+        block_on(self.vfs.create_dir(path))
+    }
+
+    fn create_dir_all(&self, path: &AbsolutePath) -> VfsResult<()> {
+        block_on(self.vfs.create_dir_all(path))
+    }
+
+    fn lstat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+        block_on(self.vfs.lstat(path))
+    }
+
+    fn read_link(&self, path: &AbsolutePath) -> VfsResult<String> {
+        block_on(self.vfs.read_link(path))
+    }
+
+    fn create_symlink(&self, target: &str, link: &AbsolutePath) -> VfsResult<()> {
+        block_on(self.vfs.create_symlink(target, link))
+    }
+
+    fn rename(&self, from: &AbsolutePath, to: &AbsolutePath) -> VfsResult<()> {
+        block_on(self.vfs.rename(from, to))
+    }
+
+    fn take_notices(&self) -> Vec<VfsNotice> {
+        self.vfs.take_notices()
+    }
+}
+
+/// Uses a [`Vfs`] as an [`AsyncVfs`], calls run one after the other on a
+/// thread of the adapter so that the caller is never blocked.
+pub struct ThreadedVfs<V> {
+    vfs: Arc<V>,
+    worker: Worker,
+}
+
+impl<V: Vfs + 'static> ThreadedVfs<V> {
+    pub fn new(vfs: V) -> ThreadedVfs<V> {
+        ThreadedVfs {
+            vfs: Arc::new(vfs),
+            worker: Worker::new(),
+        }
+    }
+
+    /// Runs `call` on the adapter's thread and waits for it without
+    /// blocking.
+    async fn run<T: Send + 'static>(
+        &self,
+        call: impl FnOnce(&V) -> VfsResult<T> + Send + 'static,
+    ) -> VfsResult<T> {
+        let vfs = self.vfs.clone();
+        self.worker.run(move || call(&vfs)).await
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A thread that runs calls in order, it ends when the adapters that share
+/// it are dropped.
+#[derive(Clone)]
+struct Worker {
+    jobs: mpsc::Sender<Job>,
+}
+
+impl Worker {
+    fn new() -> Worker {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        thread::spawn(move || {
+            for job in receiver {
+                // a call that panics fails on its own, the next ones still run
+                let _ = panic::catch_unwind(AssertUnwindSafe(job));
+            }
+        });
+        Worker { jobs }
+    }
+
+    async fn run<T: Send + 'static>(
+        &self,
+        call: impl FnOnce() -> VfsResult<T> + Send + 'static,
+    ) -> VfsResult<T> {
+        let (sender, receiver) = oneshot::channel();
+        self.jobs
+            .send(Box::new(move || {
+                let _ = sender.send(call());
+            }))
+            .map_err(|_| VfsError::Io("the filesystem thread stopped".to_string()))?;
+        receiver
+            .await
+            .unwrap_or_else(|_| Err(VfsError::Io("the filesystem call panicked".to_string())))
+    }
+}
+
+/// Uses a [`File`] as an [`AsyncFile`], calls run on a thread of the
+/// adapter, or on the thread of the [`ThreadedVfs`] that opened the file.
+pub struct ThreadedFile {
+    /// `None` if a call panicked and took the file with it
+    file: Option<Box<dyn File>>,
+    worker: Worker,
+}
+
+impl ThreadedFile {
+    pub fn new(file: Box<dyn File>) -> ThreadedFile {
+        ThreadedFile::on(Worker::new(), file)
+    }
+
+    fn on(worker: Worker, file: Box<dyn File>) -> ThreadedFile {
+        ThreadedFile {
+            file: Some(file),
+            worker,
+        }
+    }
+
+    async fn run<T: Send + 'static>(
+        &mut self,
+        call: impl FnOnce(&mut Box<dyn File>) -> VfsResult<T> + Send + 'static,
+    ) -> VfsResult<T> {
+        let mut file = self
+            .file
+            .take()
+            .ok_or_else(|| VfsError::Io("the file was lost by a failed call".to_string()))?;
+        let (file, result) = self
+            .worker
+            .run(move || {
+                let result = call(&mut file);
+                Ok((file, result))
+            })
+            .await?;
+        self.file = Some(file);
+        result
+    }
+}
+
+#[async_trait]
+impl AsyncFile for ThreadedFile {
+    async fn read(&mut self, buffer: &mut [u8]) -> VfsResult<u64> {
+        let len = buffer.len();
+        let data = self
+            .run(move |file| {
+                let mut data = vec![0; len];
+                let read = file.read(&mut data)? as usize;
+                data.truncate(read.min(len));
+                Ok(data)
+            })
+            .await?;
+        buffer[..data.len()].copy_from_slice(&data);
+        Ok(data.len() as u64)
+    }
+
+    async fn write(&mut self, buffer: &[u8]) -> VfsResult<u64> {
+        let data = buffer.to_vec();
+        self.run(move |file| file.write(&data)).await
+    }
+
+    async fn seek(&mut self, from: Seek) -> VfsResult<u64> {
+        self.run(move |file| file.seek(from)).await
+    }
+}
+
+#[async_trait]
+impl<V: Vfs + 'static> AsyncVfs for ThreadedVfs<V> {
+    async fn open(
+        &self,
+        path: &AbsolutePath,
+        open_options: OpenOptions,
+    ) -> VfsResult<Box<dyn AsyncFile>> {
+        let path = path.clone();
+        let file = self.run(move |vfs| vfs.open(&path, open_options)).await?;
+        Ok(Box::new(ThreadedFile::on(self.worker.clone(), file)))
+    }
+
+    async fn unlink(&self, path: &AbsolutePath) -> VfsResult<()> {
+        let path = path.clone();
+        self.run(move |vfs| vfs.unlink(&path)).await
+    }
+
+    async fn stat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+        let path = path.clone();
+        self.run(move |vfs| vfs.stat(&path)).await
+    }
+
+    async fn read_dir(&self, path: &AbsolutePath) -> VfsResult<Vec<AbsolutePath>> {
+        let path = path.clone();
+        self.run(move |vfs| vfs.read_dir(&path)).await
+    }
+
+    async fn read_dir_with_stat(&self, path: &AbsolutePath) -> VfsResult<Vec<DirEntry>> {
+        let path = path.clone();
+        self.run(move |vfs| vfs.read_dir_with_stat(&path)).await
+    }
+
+    async fn create_dir(&self, path: &AbsolutePath) -> VfsResult<()> {
+        let path = path.clone();
+        self.run(move |vfs| vfs.create_dir(&path)).await
+    }
+
+    async fn create_dir_all(&self, path: &AbsolutePath) -> VfsResult<()> {
+        let path = path.clone();
+        self.run(move |vfs| vfs.create_dir_all(&path)).await
+    }
+
+    async fn lstat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+        let path = path.clone();
+        self.run(move |vfs| vfs.lstat(&path)).await
+    }
+
+    async fn read_link(&self, path: &AbsolutePath) -> VfsResult<String> {
+        let path = path.clone();
+        self.run(move |vfs| vfs.read_link(&path)).await
+    }
+
+    async fn create_symlink(&self, target: &str, link: &AbsolutePath) -> VfsResult<()> {
+        let target = target.to_string();
+        let link = link.clone();
+        self.run(move |vfs| vfs.create_symlink(&target, &link))
+            .await
+    }
+
+    async fn rename(&self, from: &AbsolutePath, to: &AbsolutePath) -> VfsResult<()> {
+        let from = from.clone();
+        let to = to.clone();
+        self.run(move |vfs| vfs.rename(&from, &to)).await
+    }
+
+    fn take_notices(&self) -> Vec<VfsNotice> {
+        self.vfs.take_notices()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, thread};
+
+    use super::{block_on, BlockingVfs, ThreadedVfs, Worker};
+    use crate::{AbsolutePath, AsyncVfs, File, OpenOptions, Seek, Stat, Vfs, VfsError, VfsResult};
+
+    /// A filesystem with a single file, `/file`.
+    struct OneFile {
+        contents: Mutex<Vec<u8>>,
+    }
+
+    struct Cursor {
+        data: Vec<u8>,
+        position: usize,
+    }
+
+    impl File for Cursor {
+        fn read(&mut self, buffer: &mut [u8]) -> VfsResult<u64> {
+            let read = buffer.len().min(self.data.len() - self.position);
+            buffer[..read].copy_from_slice(&self.data[self.position..self.position + read]);
+            self.position += read;
+            Ok(read as u64)
+        }
+
+        fn write(&mut self, _buffer: &[u8]) -> VfsResult<u64> {
+            Err(VfsError::PermissionDenied)
+        }
+
+        fn seek(&mut self, from: Seek) -> VfsResult<u64> {
+            match from {
+                Seek::Start(offset) => self.position = offset as usize,
+                _ => return Err(VfsError::Unsupported),
+            }
+            Ok(self.position as u64)
+        }
+    }
+
+    impl OneFile {
+        fn check(&self, path: &AbsolutePath) -> VfsResult<()> {
+            match path.as_str() {
+                "/file" => Ok(()),
+                _ => Err(VfsError::NotFound),
+            }
+        }
+    }
+
+    impl Vfs for OneFile {
+        fn open(&self, path: &AbsolutePath, _: OpenOptions) -> VfsResult<Box<dyn File>> {
+            self.check(path)?;
+            Ok(Box::new(Cursor {
+                data: self.contents.lock().unwrap().clone(),
+                position: 0,
+            }))
+        }
+
+        fn unlink(&self, _: &AbsolutePath) -> VfsResult<()> {
+            Err(VfsError::Unsupported)
+        }
+
+        fn stat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+            self.check(path)?;
+            Ok(Stat::new(
+                crate::Kind::File,
+                self.contents.lock().unwrap().len() as u64,
+            ))
+        }
+
+        fn read_dir(&self, _: &AbsolutePath) -> VfsResult<Vec<AbsolutePath>> {
+            Ok(vec![AbsolutePath::new("/file")])
+        }
+
+        fn create_dir(&self, _: &AbsolutePath) -> VfsResult<()> {
+            Err(VfsError::Unsupported)
+        }
+
+        fn create_dir_all(&self, _: &AbsolutePath) -> VfsResult<()> {
+            Err(VfsError::Unsupported)
+        }
+
+        fn lstat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+            self.stat(path)
+        }
+
+        fn read_link(&self, _: &AbsolutePath) -> VfsResult<String> {
+            Err(VfsError::Unsupported)
+        }
+
+        fn create_symlink(&self, _: &str, _: &AbsolutePath) -> VfsResult<()> {
+            Err(VfsError::Unsupported)
+        }
+
+        fn rename(&self, _: &AbsolutePath, _: &AbsolutePath) -> VfsResult<()> {
+            Err(VfsError::Unsupported)
+        }
+    }
+
+    fn one_file() -> OneFile {
+        OneFile {
+            contents: Mutex::new(b"junkyard".to_vec()),
+        }
+    }
+
+    #[test]
+    fn threaded() {
+        let vfs = ThreadedVfs::new(one_file());
+        let path = AbsolutePath::new("/file");
+
+        let entries = block_on(vfs.read_dir_with_stat(&AbsolutePath::new("/"))).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].stat.size, 8);
+        assert_eq!(
+            block_on(vfs.stat(&AbsolutePath::new("/missing"))),
+            Err(VfsError::NotFound)
+        );
+
+        let mut file = block_on(vfs.open(&path, OpenOptions::read())).unwrap();
+        let mut buffer = [0; 4];
+        assert_eq!(block_on(file.seek(Seek::Start(4))), Ok(4));
+        assert_eq!(block_on(file.read(&mut buffer)), Ok(4));
+        assert_eq!(&buffer, b"yard");
+        assert_eq!(block_on(file.read(&mut buffer)), Ok(0));
+    }
+
+    #[test]
+    fn one_thread_per_adapter() {
+        let worker = Worker::new();
+        let thread = || block_on(worker.run(|| Ok(thread::current().id()))).unwrap();
+
+        let first = thread();
+        assert_ne!(first, thread::current().id());
+        assert_eq!(thread(), first);
+        // the thread outlives a call that panics
+        assert!(block_on(worker.run(|| -> VfsResult<()> { panic!("call") })).is_err());
+        assert_eq!(thread(), first);
+    }
+
+    #[test]
+    fn both_ways() {
+        let vfs = BlockingVfs::new(ThreadedVfs::new(one_file()));
+        let path = AbsolutePath::new("/file");
+
+        assert_eq!(vfs.stat(&path).map(|stat| stat.size), Ok(8));
+        assert_eq!(
+            vfs.read_dir(&AbsolutePath::new("/")),
+            Ok(vec![path.clone()])
+        );
+        let mut file = vfs.open(&path, OpenOptions::read()).unwrap();
+        let mut buffer = [0; 16];
+        assert_eq!(file.read(&mut buffer), Ok(8));
+        assert_eq!(file.write(b"x"), Err(VfsError::PermissionDenied));
+    }
+}
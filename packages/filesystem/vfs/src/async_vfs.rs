@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    AbsolutePath, DirEntry, Kind, OpenOptions, Operation, Seek, Stat, VfsNotice, VfsResult,
+};
+
+/// The asynchronous version of [`crate::File`].
+#[async_trait]
+pub trait AsyncFile: Send {
+    async fn read(&mut self, buffer: &mut [u8]) -> VfsResult<u64>;
+    async fn write(&mut self, buffer: &[u8]) -> VfsResult<u64>;
+    async fn seek(&mut self, from: Seek) -> VfsResult<u64>;
+}
+
+#[async_trait]
+impl<F: AsyncFile + ?Sized> AsyncFile for Box<F> {
+    async fn read(&mut self, buffer: &mut [u8]) -> VfsResult<u64> {
+        (**self).read(buffer).await
+    }
+
+    async fn write(&mut self, buffer: &[u8]) -> VfsResult<u64> {
+        (**self).write(buffer).await
+    }
+
+    async fn seek(&mut self, from: Seek) -> VfsResult<u64> {
+        (**self).seek(from).await
+    }
+}
+
+/// The asynchronous version of [`crate::Vfs`], for filesystems that should
+/// not block the caller while they work. See [`crate::Vfs`] for what every
+/// call does.
+#[async_trait]
+pub trait AsyncVfs: Send + Sync {
+    // Files
+    async fn open(
+        &self,
+        path: &AbsolutePath,
+        open_options: OpenOptions,
+    ) -> VfsResult<Box<dyn AsyncFile>>;
+    async fn unlink(&self, path: &AbsolutePath) -> VfsResult<()>;
+    async fn stat(&self, path: &AbsolutePath) -> VfsResult<Stat>;
+
+    // Folders
+    async fn read_dir(&self, path: &AbsolutePath) -> VfsResult<Vec<AbsolutePath>>;
+    async fn read_dir_with_stat(&self, path: &AbsolutePath) -> VfsResult<Vec<DirEntry>> {
+        let mut entries = vec![];
+        for entry in self.read_dir(path).await? {
+            entries.push(DirEntry {
+                name: entry.name().to_string(),
+                stat: self
+                    .lstat(&entry)
+                    .await
+                    .unwrap_or_else(|_| Stat::new(Kind::Unknown, 0)),
+            });
+        }
+        Ok(entries)
+    }
+    async fn create_dir(&self, path: &AbsolutePath) -> VfsResult<()>;
+    async fn create_dir_all(&self, path: &AbsolutePath) -> VfsResult<()>;
+
+    // Links
+    async fn lstat(&self, path: &AbsolutePath) -> VfsResult<Stat>;
+    async fn read_link(&self, path: &AbsolutePath) -> VfsResult<String>;
+    async fn create_symlink(&self, target: &str, link: &AbsolutePath) -> VfsResult<()>;
+
+    // All
+    async fn rename(&self, from: &AbsolutePath, to: &AbsolutePath) -> VfsResult<()>;
+
+    fn take_notices(&self) -> Vec<VfsNotice> {
+        vec![]
+    }
+
+    /// The same filesystem, but its calls report their progress to
+    /// `operation` and stop early once it is cancelled. `None` if the
+    /// filesystem can do neither.
+    fn with_operation(&self, _operation: Arc<Operation>) -> Option<Arc<dyn AsyncVfs>> {
+        None
+    }
+}
@@ -1,7 +1,16 @@
+pub use adapters::{block_on, BlockingFile, BlockingVfs, ThreadedFile, ThreadedVfs};
+pub use async_vfs::{AsyncFile, AsyncVfs};
+pub use operation::{Operation, Progress};
+pub use stream::FileStream;
 pub use wasm_vfs_api::AbsolutePath;
-pub use wasm_vfs_api::{Kind, Seek, Stat};
+pub use wasm_vfs_api::{Datetime, DirEntry, Kind, Seek, Stat, VfsError};
 
-pub type VfsResult<T> = Result<T, String>;
+mod adapters;
+mod async_vfs;
+mod operation;
+mod stream;
+
+pub type VfsResult<T> = Result<T, VfsError>;
 // pub use stat::{Kind, Stat};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -66,12 +75,49 @@ impl OpenOptions {
     }
 }
 
-pub trait File {
+/// Something the user should be told about, that happened while a
+/// filesystem was serving calls.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VfsNotice {
+    /// The plugin crashed and was started again, files opened before are
+    /// closed.
+    Restarted { reason: String },
+    /// The plugin crashed too many times and is not started again.
+    Disabled { reason: String },
+}
+
+impl VfsNotice {
+    pub fn message(&self) -> String {
+        match self {
+            VfsNotice::Restarted { reason } => format!("The plugin was restarted: {}", reason),
+            VfsNotice::Disabled { reason } => format!("The plugin was disabled: {}", reason),
+        }
+    }
+}
+
+/// Reads and writes may transfer fewer bytes than requested, a read that
+/// returns 0 means the end of the file. Use [`FileStream`] to get the
+/// [`std::io`] helpers that retry partial transfers.
+pub trait File: Send {
     fn read(&mut self, buffer: &mut [u8]) -> VfsResult<u64>;
     fn write(&mut self, buffer: &[u8]) -> VfsResult<u64>;
     fn seek(&mut self, from: Seek) -> VfsResult<u64>;
 }
 
+impl<F: File + ?Sized> File for Box<F> {
+    fn read(&mut self, buffer: &mut [u8]) -> VfsResult<u64> {
+        (**self).read(buffer)
+    }
+
+    fn write(&mut self, buffer: &[u8]) -> VfsResult<u64> {
+        (**self).write(buffer)
+    }
+
+    fn seek(&mut self, from: Seek) -> VfsResult<u64> {
+        (**self).seek(from)
+    }
+}
+
 pub trait Vfs: Send + Sync {
     // Files
     fn open(&self, path: &AbsolutePath, open_options: OpenOptions) -> VfsResult<Box<dyn File>>;
@@ -80,20 +126,54 @@ pub trait Vfs: Send + Sync {
 
     // Folders
     fn read_dir(&self, path: &AbsolutePath) -> VfsResult<Vec<AbsolutePath>>;
+    /// Lists a folder together with the metadata of every entry, as returned
+    /// by [`Vfs::lstat`]. Entries whose metadata cannot be read are reported
+    /// with an unknown kind.
+    ///
+    /// Filesystems that can read the metadata while listing the folder should
+    /// override this, the default implementation stats every entry separately.
+    fn read_dir_with_stat(&self, path: &AbsolutePath) -> VfsResult<Vec<DirEntry>> {
+        Ok(self
+            .read_dir(path)?
+            .into_iter()
+            .map(|entry| DirEntry {
+                name: entry.name().to_string(),
+                stat: self
+                    .lstat(&entry)
+                    .unwrap_or_else(|_| Stat::new(Kind::Unknown, 0)),
+            })
+            .collect())
+    }
     fn create_dir(&self, path: &AbsolutePath) -> VfsResult<()>;
     fn create_dir_all(&self, path: &AbsolutePath) -> VfsResult<()>;
 
+    // Links
+    /// Same as [`Vfs::stat`], but returns the metadata of the link itself
+    /// instead of following it.
+    fn lstat(&self, path: &AbsolutePath) -> VfsResult<Stat>;
+    /// Returns the target of a symbolic link, exactly as it was stored,
+    /// which means that it might be a relative path.
+    fn read_link(&self, path: &AbsolutePath) -> VfsResult<String>;
+    fn create_symlink(&self, target: &str, link: &AbsolutePath) -> VfsResult<()>;
+
     // All
     fn rename(&self, from: &AbsolutePath, to: &AbsolutePath) -> VfsResult<()>;
+
+    /// Returns the notices gathered since the last call.
+    fn take_notices(&self) -> Vec<VfsNotice> {
+        vec![]
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::Vfs;
+    use crate::{AsyncVfs, Vfs};
 
     #[test]
     fn object_safe() {
         #[allow(unused)]
         fn dispatch(_vfs: &dyn Vfs) {}
+        #[allow(unused)]
+        fn dispatch_async(_vfs: &dyn AsyncVfs) {}
     }
 }
@@ -0,0 +1,52 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
+/// How far an [`Operation`] got.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Progress {
+    pub done: u64,
+    /// 0 if it is not known
+    pub total: u64,
+    pub message: String,
+}
+
+/// A call that may take long, the filesystem reports its progress to the
+/// caller, and the caller may cancel it.
+#[derive(Debug, Default)]
+pub struct Operation {
+    cancelled: AtomicBool,
+    progress: Mutex<Progress>,
+}
+
+impl Operation {
+    pub fn new() -> Operation {
+        Operation::default()
+    }
+
+    /// Asks the filesystem to stop, calls that notice fail with
+    /// [`crate::VfsError::Cancelled`].
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub fn report(&self, progress: Progress) {
+        *self
+            .progress
+            .lock()
+            .unwrap_or_else(|error| error.into_inner()) = progress;
+    }
+
+    /// The last progress reported.
+    pub fn progress(&self) -> Progress {
+        self.progress
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+}
@@ -0,0 +1,97 @@
+use std::io;
+
+use crate::{File, Seek};
+
+/// Adapts a [`File`] to the [`std::io`] traits, so that it can be used with
+/// [`io::copy`] and buffered readers or writers. Reads and writes are passed
+/// through as they are, partial writes are retried by [`io::Write::write_all`].
+pub struct FileStream<F: File> {
+    file: F,
+}
+
+impl<F: File> FileStream<F> {
+    pub fn new(file: F) -> FileStream<F> {
+        FileStream { file }
+    }
+
+    pub fn into_inner(self) -> F {
+        self.file
+    }
+}
+
+impl<F: File> io::Read for FileStream<F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        Ok(self.file.read(buf)? as usize)
+    }
+}
+
+impl<F: File> io::Write for FileStream<F> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        Ok(self.file.write(buf)? as usize)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<F: File> io::Seek for FileStream<F> {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        Ok(self.file.seek(match pos {
+            io::SeekFrom::Start(offset) => Seek::Start(offset),
+            io::SeekFrom::End(offset) => Seek::End(offset),
+            io::SeekFrom::Current(offset) => Seek::Current(offset),
+        })?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Read, Seek as _, Write};
+
+    use crate::{File, Seek, VfsResult};
+
+    use super::FileStream;
+
+    /// An in memory file that accepts at most `chunk` bytes per call.
+    struct ChunkedFile {
+        data: io::Cursor<Vec<u8>>,
+        chunk: usize,
+    }
+
+    impl File for ChunkedFile {
+        fn read(&mut self, buffer: &mut [u8]) -> VfsResult<u64> {
+            let len = buffer.len().min(self.chunk);
+            Ok(self.data.read(&mut buffer[..len])? as u64)
+        }
+
+        fn write(&mut self, buffer: &[u8]) -> VfsResult<u64> {
+            let len = buffer.len().min(self.chunk);
+            Ok(self.data.write(&buffer[..len])? as u64)
+        }
+
+        fn seek(&mut self, from: Seek) -> VfsResult<u64> {
+            Ok(self.data.seek(match from {
+                Seek::Start(offset) => io::SeekFrom::Start(offset),
+                Seek::End(offset) => io::SeekFrom::End(offset),
+                Seek::Current(offset) => io::SeekFrom::Current(offset),
+            })?)
+        }
+    }
+
+    #[test]
+    fn copy_in_chunks() {
+        let source: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+        let mut stream = FileStream::new(ChunkedFile {
+            data: io::Cursor::new(vec![]),
+            chunk: 7,
+        });
+
+        io::copy(&mut source.as_slice(), &mut stream).unwrap();
+        stream.rewind().unwrap();
+        let mut copy = vec![];
+        stream.read_to_end(&mut copy).unwrap();
+
+        assert_eq!(copy, source);
+    }
+}
@@ -1,59 +1,210 @@
-use std::fs::{self};
+use std::fs;
+use std::io::{self, Read, Seek as _, Write};
+
+#[cfg(target_os = "wasi")]
+mod wasi_fs;
 
 use wasm_vfs::{
-    create_absolute_path, export_vfs, AbsolutePath, File, FileResource, Filesystem, Kind, Seek,
-    Stat,
+    create_absolute_path, export_vfs, AbsolutePath, Capability, DirEntry, File, FileResource,
+    Filesystem, Kind, MountConfig, OpenFlags, PluginInfo, Seek, Stat, VfsError,
 };
 
-struct LocalFile;
+struct LocalFile(fs::File);
 
 impl File for LocalFile {
-    fn read(&self, _data: Vec<u8>) -> Result<u64, String> {
-        todo!()
+    fn read(&self, len: u64) -> Result<Vec<u8>, VfsError> {
+        let mut data = vec![];
+        (&self.0).take(len).read_to_end(&mut data)?;
+        Ok(data)
     }
 
-    fn write(&self, _data: Vec<u8>) -> Result<u64, String> {
-        todo!()
+    fn write(&self, data: Vec<u8>) -> Result<u64, VfsError> {
+        Ok((&self.0).write(&data)? as u64)
     }
 
-    fn seek(&self, _s: Seek) -> Result<u64, String> {
-        todo!()
+    fn seek(&self, s: Seek) -> Result<u64, VfsError> {
+        Ok((&self.0).seek(match s {
+            Seek::Start(offset) => io::SeekFrom::Start(offset),
+            Seek::Current(offset) => io::SeekFrom::Current(offset),
+            Seek::End(offset) => io::SeekFrom::End(offset),
+        })?)
     }
 }
 
 struct LocalVfs;
 
+impl LocalVfs {
+    /// Serves the folders preopened for the plugin, which can only be
+    /// mounted whole.
+    fn mount(config: MountConfig) -> Result<LocalVfs, VfsError> {
+        match config.uri.as_str() {
+            "file://" | "file:///" => Ok(LocalVfs),
+            uri => Err(VfsError::InvalidPath(format!("{} cannot be mounted", uri))),
+        }
+    }
+}
+
+fn stat_from_metadata(data: &fs::Metadata) -> Stat {
+    let kind = if data.is_dir() {
+        Kind::Folder
+    } else if data.is_symlink() {
+        Kind::Link
+    } else if data.is_file() {
+        Kind::File
+    } else {
+        Kind::Unknown
+    };
+    // WASI does not expose ownership and permission bits, so those are left empty
+    let mut stat = Stat::new(kind, data.len());
+    stat.modified = data.modified().ok().map(Into::into);
+    stat.accessed = data.accessed().ok().map(Into::into);
+    stat.created = data.created().ok().map(Into::into);
+    stat
+}
+
 impl Filesystem for LocalVfs {
-    fn read_dir(&self, path: &AbsolutePath) -> Result<Vec<AbsolutePath>, String> {
-        let entries = fs::read_dir(path.path()).map_err(|e| e.to_string())?;
+    fn read_dir(&self, path: &AbsolutePath) -> Result<Vec<AbsolutePath>, VfsError> {
+        let entries = fs::read_dir(path.path())?;
+        let mut files = vec![];
+        for entry in entries.flatten() {
+            files.push(create_absolute_path(&entry.path().to_string_lossy()));
+        }
+        Ok(files)
+    }
+
+    fn read_dir_with_stat(&self, path: &AbsolutePath) -> Result<Vec<DirEntry>, VfsError> {
+        let entries = fs::read_dir(path.path())?;
         let mut files = vec![];
-        for entry in entries {
-            if let Ok(entry) = entry {
-                files.push(create_absolute_path(&entry.path().to_string_lossy()));
-            }
+        for entry in entries.flatten() {
+            let stat = entry
+                .metadata()
+                .map(|data| stat_from_metadata(&data))
+                .unwrap_or_else(|_| Stat::new(Kind::Unknown, 0));
+            files.push(DirEntry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                stat,
+            });
         }
         Ok(files)
     }
 
-    fn stat(&self, path: &AbsolutePath) -> Result<Stat, String> {
-        let data = fs::metadata(path.path()).map_err(|e| e.to_string())?;
-        Ok(Stat {
-            kind: if data.is_dir() {
-                Kind::Folder
-            } else if data.is_symlink() {
-                Kind::Link
-            } else if data.is_file() {
-                Kind::File
-            } else {
-                Kind::Unknown
-            },
-            size: data.len(),
-        })
+    fn stat(&self, path: &AbsolutePath) -> Result<Stat, VfsError> {
+        Ok(stat_from_metadata(&fs::metadata(path.path())?))
+    }
+
+    fn lstat(&self, path: &AbsolutePath) -> Result<Stat, VfsError> {
+        Ok(stat_from_metadata(&fs::symlink_metadata(path.path())?))
+    }
+
+    fn read_link(&self, path: &AbsolutePath) -> Result<String, VfsError> {
+        Ok(fs::read_link(path.path())?.to_string_lossy().into_owned())
+    }
+
+    #[cfg(target_os = "wasi")]
+    fn create_symlink(&self, target: String, link: &AbsolutePath) -> Result<(), VfsError> {
+        wasi_fs::symlink(&target, &link.path())
+    }
+
+    #[cfg(not(target_os = "wasi"))]
+    fn create_symlink(&self, _target: String, _link: &AbsolutePath) -> Result<(), VfsError> {
+        Err(VfsError::Unsupported)
     }
 
-    fn open(&self, _path: AbsolutePath) -> Result<FileResource, String> {
-        todo!()
+    fn open(&self, path: &AbsolutePath, options: OpenFlags) -> Result<FileResource, VfsError> {
+        let file = fs::OpenOptions::new()
+            .create(options.contains(OpenFlags::CREATE))
+            .read(options.contains(OpenFlags::READ))
+            .write(options.contains(OpenFlags::WRITE))
+            .truncate(options.contains(OpenFlags::TRUNCATE))
+            .append(options.contains(OpenFlags::APPEND))
+            .open(path.path())?;
+        Ok(FileResource::new(LocalFile(file)))
+    }
+
+    fn unlink(&self, path: &AbsolutePath) -> Result<(), VfsError> {
+        Ok(fs::remove_file(path.path())?)
+    }
+
+    fn create_dir(&self, path: &AbsolutePath) -> Result<(), VfsError> {
+        Ok(fs::create_dir(path.path())?)
+    }
+
+    fn create_dir_all(&self, path: &AbsolutePath) -> Result<(), VfsError> {
+        Ok(fs::create_dir_all(path.path())?)
+    }
+
+    fn rename(&self, from: &AbsolutePath, to: &AbsolutePath) -> Result<(), VfsError> {
+        Ok(fs::rename(from.path(), to.path())?)
     }
 }
 
-export_vfs!(LocalVfs, LocalFile, LocalVfs);
+export_vfs!(
+    LocalVfs,
+    LocalFile,
+    LocalVfs::mount,
+    PluginInfo {
+        name: env!("CARGO_PKG_NAME").to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        author: String::new(),
+        schemes: vec!["file".to_string()],
+        capabilities: vec![
+            Capability::Read,
+            Capability::Write,
+            Capability::Links,
+            Capability::Rename,
+        ],
+        config_schema: String::new(),
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs};
+
+    use wasm_vfs::{File, MountConfig, Seek, VfsError};
+
+    use crate::{LocalFile, LocalVfs};
+
+    #[test]
+    fn mount() {
+        let config = |uri: &str| MountConfig {
+            uri: uri.to_string(),
+            options: vec![],
+        };
+        assert!(LocalVfs::mount(config("file:///")).is_ok());
+        assert!(matches!(
+            LocalVfs::mount(config("file:///home")),
+            Err(VfsError::InvalidPath(_))
+        ));
+    }
+
+    #[test]
+    fn read_write_seek() {
+        let mut path = temp_dir();
+        path.push("wasm_local_fs_file_test");
+        let file = LocalFile(
+            fs::OpenOptions::new()
+                .create(true)
+                .read(true)
+                .write(true)
+                .truncate(true)
+                .open(&path)
+                .unwrap(),
+        );
+
+        let written = file.write(b"junkyard".to_vec()).unwrap();
+        let position = file.seek(Seek::Start(4)).unwrap();
+        let tail = file.read(100).unwrap();
+        let end = file.read(100).unwrap();
+        file.seek(Seek::End(-8)).unwrap();
+        let head = file.read(4).unwrap();
+
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(written, 8);
+        assert_eq!(position, 4);
+        assert_eq!(tail, b"yard");
+        assert_eq!(end, b"");
+        assert_eq!(head, b"junk");
+    }
+}
@@ -0,0 +1,47 @@
+//! Operations that the standard library does not expose on WASI yet,
+//! implemented directly on top of the preopened directories.
+
+use wasi::filesystem::{
+    preopens,
+    types::{Descriptor, ErrorCode},
+};
+use wasm_vfs::VfsError;
+
+/// Finds the preopened directory that contains `path` and returns it together
+/// with `path` relative to that directory.
+fn resolve(path: &str) -> Result<(Descriptor, String), VfsError> {
+    preopens::get_directories()
+        .into_iter()
+        .filter(|(_, guest_path)| {
+            let prefix = format!("{}/", guest_path.trim_end_matches('/'));
+            path == guest_path || path.starts_with(&prefix)
+        })
+        .max_by_key(|(_, guest_path)| guest_path.len())
+        .map(|(directory, guest_path)| {
+            let relative = path[guest_path.len()..].trim_start_matches('/');
+            (directory, relative.to_string())
+        })
+        .ok_or(VfsError::NotFound)
+}
+
+fn to_vfs_error(error: ErrorCode) -> VfsError {
+    match error {
+        ErrorCode::NoEntry => VfsError::NotFound,
+        ErrorCode::Access | ErrorCode::NotPermitted | ErrorCode::ReadOnly => {
+            VfsError::PermissionDenied
+        }
+        ErrorCode::Exist => VfsError::AlreadyExists,
+        ErrorCode::NotDirectory => VfsError::NotADirectory,
+        ErrorCode::IsDirectory => VfsError::IsADirectory,
+        ErrorCode::NotEmpty => VfsError::NotEmpty,
+        ErrorCode::Unsupported => VfsError::Unsupported,
+        error => VfsError::Io(error.to_string()),
+    }
+}
+
+pub(crate) fn symlink(target: &str, link: &str) -> Result<(), VfsError> {
+    let (directory, relative_link) = resolve(link)?;
+    directory
+        .symlink_at(target, &relative_link)
+        .map_err(to_vfs_error)
+}
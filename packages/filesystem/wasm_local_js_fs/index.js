@@ -1,4 +1,4 @@
-import { createAbsolutePath } from "junkyard-vfs:vfs-plugin/vfs-host";
+import { createAbsolutePath } from "junkyard-vfs:vfs-plugin/vfs-host@1.0.0";
 class Vfs {
     fs = {
         "dir1": {},
@@ -12,6 +12,16 @@ class Vfs {
         }
         return files;
     }
+    readDirWithStat(path) {
+        let entries = [];
+        for (const file in this.fs) {
+            entries.push({
+                name: file,
+                stat: this.stat(createAbsolutePath("/" + file)),
+            });
+        }
+        return entries;
+    }
     stat(path) {
         let parts = path.components();
         if (this.fs[parts[0]] instanceof Object) {
@@ -27,23 +37,58 @@ class Vfs {
             };
         }
     }
-    open(path) {
-        throw new Error("Method not implemented.");
+    lstat(path) {
+        // there are no links in this filesystem
+        return this.stat(path);
+    }
+    readLink(path) {
+        throw { tag: "unsupported" };
+    }
+    createSymlink(target, link) {
+        throw { tag: "unsupported" };
+    }
+    open(path, options) {
+        throw { tag: "unsupported" };
+    }
+    unlink(path) {
+        throw { tag: "unsupported" };
+    }
+    createDir(path) {
+        throw { tag: "unsupported" };
+    }
+    createDirAll(path) {
+        throw { tag: "unsupported" };
+    }
+    rename(fromPath, toPath) {
+        throw { tag: "unsupported" };
     }
 }
 class MyFile {
-    read(data) {
-        throw new Error("Method not implemented.");
+    read(len) {
+        throw { tag: "unsupported" };
     }
     write(data) {
-        throw new Error("Method not implemented.");
+        throw { tag: "unsupported" };
     }
     seek(s) {
-        throw new Error("Method not implemented.");
+        throw { tag: "unsupported" };
     }
 }
 export const vfs = {
-    init() {
+    info() {
+        return {
+            name: "wasm_local_js_fs",
+            version: "0.1.0",
+            author: "",
+            schemes: ["memory"],
+            capabilities: ["read"],
+            configSchema: "",
+        };
+    },
+    init(config) {
+        if (config.uri != "memory:///") {
+            throw { tag: "invalid-path", val: `${config.uri} cannot be mounted` };
+        }
         return new Vfs();
     },
     File: MyFile,
@@ -1,5 +1,5 @@
-import { AbsolutePath, File, Filesystem, Seek, Stat, Kind } from "./interfaces/junkyard-vfs-vfs-plugin-vfs";
-import { createAbsolutePath } from "junkyard-vfs:vfs-plugin/vfs-host";
+import { AbsolutePath, DirEntry, File, Filesystem, MountConfig, OpenFlags, PluginInfo, Seek, Stat, Kind } from "./interfaces/junkyard-vfs-vfs-plugin-vfs";
+import { createAbsolutePath } from "junkyard-vfs:vfs-plugin/vfs-host@1.0.0";
 
 class Vfs implements Filesystem {
     fs = {
@@ -19,6 +19,16 @@ class Vfs implements Filesystem {
         }
         return files;
     }
+    readDirWithStat(path: AbsolutePath): Array<DirEntry> {
+        let entries = [] as Array<DirEntry>;
+        for (const file in this.fs) {
+            entries.push({
+                name: file,
+                stat: this.stat(createAbsolutePath("/" + file)),
+            });
+        }
+        return entries;
+    }
     stat(path: AbsolutePath): Stat {
         let parts = path.components();
         if (this.fs[parts[0]] instanceof Object) {
@@ -35,27 +45,62 @@ class Vfs implements Filesystem {
             };
         }
     }
-    open(path: AbsolutePath): File {
-        throw new Error("Method not implemented.");
+    lstat(path: AbsolutePath): Stat {
+        // there are no links in this filesystem
+        return this.stat(path);
+    }
+    readLink(path: AbsolutePath): string {
+        throw { tag: "unsupported" };
+    }
+    createSymlink(target: string, link: AbsolutePath) {
+        throw { tag: "unsupported" };
+    }
+    open(path: AbsolutePath, options: OpenFlags): File {
+        throw { tag: "unsupported" };
+    }
+    unlink(path: AbsolutePath) {
+        throw { tag: "unsupported" };
+    }
+    createDir(path: AbsolutePath) {
+        throw { tag: "unsupported" };
+    }
+    createDirAll(path: AbsolutePath) {
+        throw { tag: "unsupported" };
+    }
+    rename(fromPath: AbsolutePath, toPath: AbsolutePath) {
+        throw { tag: "unsupported" };
     }
 
 }
 
 class MyFile implements File {
-    read(data: Uint8Array): bigint {
-        throw new Error("Method not implemented.");
+    read(len: bigint): Uint8Array {
+        throw { tag: "unsupported" };
     }
     write(data: Uint8Array): bigint {
-        throw new Error("Method not implemented.");
+        throw { tag: "unsupported" };
     }
     seek(s: Seek): bigint {
-        throw new Error("Method not implemented.");
+        throw { tag: "unsupported" };
     }
 
 }
 
 export const vfs = {
-    init(): Vfs {
+    info(): PluginInfo {
+        return {
+            name: "wasm_local_js_fs",
+            version: "0.1.0",
+            author: "",
+            schemes: ["memory"],
+            capabilities: ["read"],
+            configSchema: "",
+        };
+    },
+    init(config: MountConfig): Vfs {
+        if (config.uri != "memory:///") {
+            throw { tag: "invalid-path", val: `${config.uri} cannot be mounted` };
+        }
         return new Vfs();
     },
     File: MyFile,
@@ -1,7 +1,29 @@
-declare module 'junkyard-vfs:vfs-plugin/vfs-host' {
+declare module 'junkyard-vfs:vfs-plugin/vfs-host@1.0.0' {
   export { AbsolutePath };
   export function createAbsolutePath(s: string): AbsolutePath;
+  export function log(level: LogLevel, message: string): void;
+  export function storageGet(key: string): Uint8Array | undefined;
+  export function storageSet(key: string, value: Uint8Array): void;
+  export function storageDelete(key: string): void;
+  export function storageList(): Array<string>;
+  export function promptSecret(title: string, description: string): string | undefined;
+  export function reportProgress(done: bigint, total: bigint, message: string): void;
+  export function isCancelled(): boolean;
 }
+/**
+ * # Variants
+ * 
+ * ## `"trace"`
+ * 
+ * ## `"debug"`
+ * 
+ * ## `"info"`
+ * 
+ * ## `"warn"`
+ * 
+ * ## `"error"`
+ */
+export type LogLevel = 'trace' | 'debug' | 'info' | 'warn' | 'error';
 
 export class AbsolutePath {
   components(): Array<string>;
@@ -10,3 +32,11 @@ export class AbsolutePath {
   fileName(): string;
   path(): string;
 }
+export type StorageError = StorageErrorQuotaExceeded | StorageErrorIo;
+export interface StorageErrorQuotaExceeded {
+  tag: 'quota-exceeded',
+}
+export interface StorageErrorIo {
+  tag: 'io',
+  val: string,
+}
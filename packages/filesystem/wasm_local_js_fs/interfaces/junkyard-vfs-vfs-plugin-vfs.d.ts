@@ -1,7 +1,8 @@
-declare module 'junkyard-vfs:vfs-plugin/vfs' {
+declare module 'junkyard-vfs:vfs-plugin/vfs@1.0.0' {
   export { File };
   export { Filesystem };
-  export function init(): Filesystem;
+  export function info(): PluginInfo;
+  export function init(config: MountConfig): Filesystem;
 }
 import type { AbsolutePath } from './junkyard-vfs-vfs-plugin-vfs-host.js';
 export { AbsolutePath };
@@ -30,19 +31,185 @@ export interface SeekEnd {
  * ## `"unknown"`
  */
 export type Kind = 'file' | 'folder' | 'link' | 'unknown';
+/**
+ * A point in time, as seconds and nanoseconds since the unix epoch
+ */
+export interface Datetime {
+  seconds: bigint,
+  nanoseconds: number,
+}
+/**
+ * File metadata, fields that a filesystem cannot provide are left empty
+ */
 export interface Stat {
   kind: Kind,
   size: bigint,
+  modified?: Datetime,
+  accessed?: Datetime,
+  created?: Datetime,
+  /**
+   * unix permission and file type bits
+   */
+  mode?: number,
+  uid?: number,
+  gid?: number,
+  /**
+   * name of the owner, if it can be resolved
+   */
+  user?: string,
+  /**
+   * name of the owning group, if it can be resolved
+   */
+  group?: string,
+  inode?: bigint,
+  device?: bigint,
+  nlink?: bigint,
+}
+export type VfsError = VfsErrorNotFound | VfsErrorPermissionDenied | VfsErrorAlreadyExists | VfsErrorNotADirectory | VfsErrorIsADirectory | VfsErrorNotEmpty | VfsErrorInvalidPath | VfsErrorUnsupported | VfsErrorIo | VfsErrorPluginTrap | VfsErrorTimeout | VfsErrorLimitExceeded | VfsErrorInvalidResponse | VfsErrorCancelled;
+export interface VfsErrorNotFound {
+  tag: 'not-found',
+}
+export interface VfsErrorPermissionDenied {
+  tag: 'permission-denied',
+}
+export interface VfsErrorAlreadyExists {
+  tag: 'already-exists',
+}
+export interface VfsErrorNotADirectory {
+  tag: 'not-a-directory',
+}
+export interface VfsErrorIsADirectory {
+  tag: 'is-a-directory',
+}
+export interface VfsErrorNotEmpty {
+  tag: 'not-empty',
+}
+export interface VfsErrorInvalidPath {
+  tag: 'invalid-path',
+  val: string,
+}
+export interface VfsErrorUnsupported {
+  tag: 'unsupported',
+}
+export interface VfsErrorIo {
+  tag: 'io',
+  val: string,
+}
+export interface VfsErrorPluginTrap {
+  tag: 'plugin-trap',
+  val: string,
+}
+/**
+ * the plugin did not finish the call in the allowed time
+ */
+export interface VfsErrorTimeout {
+  tag: 'timeout',
+}
+/**
+ * the plugin tried to use more memory or resources than allowed
+ */
+export interface VfsErrorLimitExceeded {
+  tag: 'limit-exceeded',
+  val: string,
+}
+/**
+ * the plugin answered with data that the host refused
+ */
+export interface VfsErrorInvalidResponse {
+  tag: 'invalid-response',
+  val: string,
+}
+export interface VfsErrorCancelled {
+  tag: 'cancelled',
+}
+/**
+ * A folder entry together with its metadata, links are not followed
+ */
+export interface DirEntry {
+  name: string,
+  stat: Stat,
+}
+
+export interface OpenFlags {
+  create?: boolean,
+  read?: boolean,
+  write?: boolean,
+  truncate?: boolean,
+  append?: boolean,
+}
+/**
+ * Something a plugin supports besides listing folders and reading
+ * metadata
+ * # Variants
+ * 
+ * ## `"read"`
+ * 
+ * ## `"write"`
+ * 
+ * ## `"links"`
+ * 
+ * ## `"rename"`
+ */
+export type Capability = 'read' | 'write' | 'links' | 'rename';
+/**
+ * What a plugin says about itself
+ */
+export interface PluginInfo {
+  name: string,
+  version: string,
+  author: string,
+  /**
+   * the URI schemes of the paths the plugin serves, like `file` or `s3`
+   */
+  schemes: Array<string>,
+  capabilities: Array<Capability>,
+  /**
+   * a JSON schema of the configuration the plugin accepts, empty if
+   * it does not take any
+   */
+  configSchema: string,
+}
+/**
+ * What a filesystem is mounted on
+ */
+export interface MountConfig {
+  /**
+   * the source of the filesystem, like `file:///home/user` or
+   * `s3://bucket`
+   */
+  uri: string,
+  /**
+   * settings described by the config schema of the plugin
+   */
+  options: Array<[string, string]>,
 }
 
 export class File {
-  read(data: Uint8Array): bigint;
+  /**
+   * reads at most `len` bytes, an empty list means end of file
+   */
+  read(len: bigint): Uint8Array;
+  /**
+   * writes a prefix of `data` and returns its length, the caller
+   * sends the rest in another call
+   */
   write(data: Uint8Array): bigint;
   seek(s: Seek): bigint;
 }
 
 export class Filesystem {
   readDir(path: AbsolutePath): Array<AbsolutePath>;
+  readDirWithStat(path: AbsolutePath): Array<DirEntry>;
   stat(path: AbsolutePath): Stat;
-  open(path: AbsolutePath): File;
+  /**
+   * like stat, but does not follow symbolic links
+   */
+  lstat(path: AbsolutePath): Stat;
+  readLink(path: AbsolutePath): string;
+  createSymlink(target: string, link: AbsolutePath): void;
+  open(path: AbsolutePath, options: OpenFlags): File;
+  unlink(path: AbsolutePath): void;
+  createDir(path: AbsolutePath): void;
+  createDirAll(path: AbsolutePath): void;
+  rename(fromPath: AbsolutePath, toPath: AbsolutePath): void;
 }
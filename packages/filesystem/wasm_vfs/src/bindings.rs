@@ -1,11 +1,13 @@
-// Generated by `wit-bindgen` 0.36.0. DO NOT EDIT!
+// Generated by `wit-bindgen` 0.41.0. DO NOT EDIT!
 // Options used:
 //   * runtime_path: "wit_bindgen_rt"
+//   * pub-export-macro
+#[rustfmt::skip]
 #[rustfmt::skip]
 #[allow(dead_code, clippy::all)]
 pub mod junkyard_vfs {
     pub mod vfs_plugin {
-        #[allow(dead_code, clippy::all)]
+        #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
         pub mod vfs_host {
             #[used]
             #[doc(hidden)]
@@ -20,7 +22,7 @@ pub mod junkyard_vfs {
                 #[doc(hidden)]
                 pub unsafe fn from_handle(handle: u32) -> Self {
                     Self {
-                        handle: _rt::Resource::from_handle(handle),
+                        handle: unsafe { _rt::Resource::from_handle(handle) },
                     }
                 }
                 #[doc(hidden)]
@@ -39,58 +41,148 @@ pub mod junkyard_vfs {
                     unreachable!();
                     #[cfg(target_arch = "wasm32")]
                     {
-                        #[link(wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host")]
-                        extern "C" {
+                        #[link(
+                            wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                        )]
+                        unsafe extern "C" {
                             #[link_name = "[resource-drop]absolute-path"]
                             fn drop(_: u32);
                         }
-                        drop(_handle);
+                        unsafe { drop(_handle) };
+                    }
+                }
+            }
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            pub enum LogLevel {
+                Trace,
+                Debug,
+                Info,
+                Warn,
+                Error,
+            }
+            impl ::core::fmt::Debug for LogLevel {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        LogLevel::Trace => f.debug_tuple("LogLevel::Trace").finish(),
+                        LogLevel::Debug => f.debug_tuple("LogLevel::Debug").finish(),
+                        LogLevel::Info => f.debug_tuple("LogLevel::Info").finish(),
+                        LogLevel::Warn => f.debug_tuple("LogLevel::Warn").finish(),
+                        LogLevel::Error => f.debug_tuple("LogLevel::Error").finish(),
+                    }
+                }
+            }
+            impl LogLevel {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> LogLevel {
+                    if !cfg!(debug_assertions) {
+                        return ::core::mem::transmute(val);
+                    }
+                    match val {
+                        0 => LogLevel::Trace,
+                        1 => LogLevel::Debug,
+                        2 => LogLevel::Info,
+                        3 => LogLevel::Warn,
+                        4 => LogLevel::Error,
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
+            /// Why a value could not be stored
+            #[derive(Clone)]
+            pub enum StorageError {
+                /// the plugin would store more than its policy allows
+                QuotaExceeded,
+                Io(_rt::String),
+            }
+            impl ::core::fmt::Debug for StorageError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        StorageError::QuotaExceeded => {
+                            f.debug_tuple("StorageError::QuotaExceeded").finish()
+                        }
+                        StorageError::Io(e) => {
+                            f.debug_tuple("StorageError::Io").field(e).finish()
+                        }
                     }
                 }
             }
+            impl ::core::fmt::Display for StorageError {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    write!(f, "{:?}", self)
+                }
+            }
+            impl std::error::Error for StorageError {}
             impl AbsolutePath {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn components(&self) -> _rt::Vec<_rt::String> {
                     unsafe {
-                        #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 2 * ::core::mem::size_of::<*const u8>()],
+                        );
                         let mut ret_area = RetArea(
-                            [::core::mem::MaybeUninit::uninit(); 8],
+                            [::core::mem::MaybeUninit::uninit(); 2
+                                * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host")]
-                        extern "C" {
+                        #[link(
+                            wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                        )]
+                        unsafe extern "C" {
                             #[link_name = "[method]absolute-path.components"]
-                            fn wit_import(_: i32, _: *mut u8);
+                            fn wit_import1(_: i32, _: *mut u8);
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32, _: *mut u8) {
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, ptr0);
-                        let l1 = *ptr0.add(0).cast::<*mut u8>();
-                        let l2 = *ptr0.add(4).cast::<usize>();
-                        let base6 = l1;
-                        let len6 = l2;
-                        let mut result6 = _rt::Vec::with_capacity(len6);
-                        for i in 0..len6 {
-                            let base = base6.add(i * 8);
-                            let e6 = {
-                                let l3 = *base.add(0).cast::<*mut u8>();
-                                let l4 = *base.add(4).cast::<usize>();
-                                let len5 = l4;
-                                let bytes5 = _rt::Vec::from_raw_parts(
-                                    l3.cast(),
-                                    len5,
-                                    len5,
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = *ptr0.add(0).cast::<*mut u8>();
+                        let l3 = *ptr0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let base7 = l2;
+                        let len7 = l3;
+                        let mut result7 = _rt::Vec::with_capacity(len7);
+                        for i in 0..len7 {
+                            let base = base7
+                                .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                            let e7 = {
+                                let l4 = *base.add(0).cast::<*mut u8>();
+                                let l5 = *base
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                let bytes6 = _rt::Vec::from_raw_parts(
+                                    l4.cast(),
+                                    len6,
+                                    len6,
                                 );
-                                _rt::string_lift(bytes5)
+                                _rt::string_lift(bytes6)
                             };
-                            result6.push(e6);
+                            result7.push(e7);
                         }
-                        _rt::cabi_dealloc(base6, len6 * 8, 4);
-                        result6
+                        _rt::cabi_dealloc(
+                            base7,
+                            len7 * (2 * ::core::mem::size_of::<*const u8>()),
+                            ::core::mem::size_of::<*const u8>(),
+                        );
+                        let result8 = result7;
+                        result8
                     }
                 }
             }
@@ -99,16 +191,18 @@ pub mod junkyard_vfs {
                 pub fn is_root(&self) -> bool {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host")]
-                        extern "C" {
+                        #[link(
+                            wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                        )]
+                        unsafe extern "C" {
                             #[link_name = "[method]absolute-path.is-root"]
-                            fn wit_import(_: i32) -> i32;
+                            fn wit_import0(_: i32) -> i32;
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32) -> i32 {
+                        unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
-                        let ret = wit_import((self).handle() as i32);
+                        let ret = unsafe { wit_import0((self).handle() as i32) };
                         _rt::bool_lift(ret as u8)
                     }
                 }
@@ -118,17 +212,19 @@ pub mod junkyard_vfs {
                 pub fn parent(&self) -> AbsolutePath {
                     unsafe {
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host")]
-                        extern "C" {
+                        #[link(
+                            wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                        )]
+                        unsafe extern "C" {
                             #[link_name = "[method]absolute-path.parent"]
-                            fn wit_import(_: i32) -> i32;
+                            fn wit_import0(_: i32) -> i32;
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32) -> i32 {
+                        unsafe extern "C" fn wit_import0(_: i32) -> i32 {
                             unreachable!()
                         }
-                        let ret = wit_import((self).handle() as i32);
-                        AbsolutePath::from_handle(ret as u32)
+                        let ret = unsafe { wit_import0((self).handle() as i32) };
+                        unsafe { AbsolutePath::from_handle(ret as u32) }
                     }
                 }
             }
@@ -136,28 +232,39 @@ pub mod junkyard_vfs {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn file_name(&self) -> _rt::String {
                     unsafe {
-                        #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 2 * ::core::mem::size_of::<*const u8>()],
+                        );
                         let mut ret_area = RetArea(
-                            [::core::mem::MaybeUninit::uninit(); 8],
+                            [::core::mem::MaybeUninit::uninit(); 2
+                                * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host")]
-                        extern "C" {
+                        #[link(
+                            wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                        )]
+                        unsafe extern "C" {
                             #[link_name = "[method]absolute-path.file-name"]
-                            fn wit_import(_: i32, _: *mut u8);
+                            fn wit_import1(_: i32, _: *mut u8);
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32, _: *mut u8) {
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, ptr0);
-                        let l1 = *ptr0.add(0).cast::<*mut u8>();
-                        let l2 = *ptr0.add(4).cast::<usize>();
-                        let len3 = l2;
-                        let bytes3 = _rt::Vec::from_raw_parts(l1.cast(), len3, len3);
-                        _rt::string_lift(bytes3)
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = *ptr0.add(0).cast::<*mut u8>();
+                        let l3 = *ptr0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let len4 = l3;
+                        let bytes4 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
+                        let result5 = _rt::string_lift(bytes4);
+                        result5
                     }
                 }
             }
@@ -165,28 +272,39 @@ pub mod junkyard_vfs {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn path(&self) -> _rt::String {
                     unsafe {
-                        #[repr(align(4))]
-                        struct RetArea([::core::mem::MaybeUninit<u8>; 8]);
+                        #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                        #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                        struct RetArea(
+                            [::core::mem::MaybeUninit<
+                                u8,
+                            >; 2 * ::core::mem::size_of::<*const u8>()],
+                        );
                         let mut ret_area = RetArea(
-                            [::core::mem::MaybeUninit::uninit(); 8],
+                            [::core::mem::MaybeUninit::uninit(); 2
+                                * ::core::mem::size_of::<*const u8>()],
                         );
                         let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
                         #[cfg(target_arch = "wasm32")]
-                        #[link(wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host")]
-                        extern "C" {
+                        #[link(
+                            wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                        )]
+                        unsafe extern "C" {
                             #[link_name = "[method]absolute-path.path"]
-                            fn wit_import(_: i32, _: *mut u8);
+                            fn wit_import1(_: i32, _: *mut u8);
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        fn wit_import(_: i32, _: *mut u8) {
+                        unsafe extern "C" fn wit_import1(_: i32, _: *mut u8) {
                             unreachable!()
                         }
-                        wit_import((self).handle() as i32, ptr0);
-                        let l1 = *ptr0.add(0).cast::<*mut u8>();
-                        let l2 = *ptr0.add(4).cast::<usize>();
-                        let len3 = l2;
-                        let bytes3 = _rt::Vec::from_raw_parts(l1.cast(), len3, len3);
-                        _rt::string_lift(bytes3)
+                        unsafe { wit_import1((self).handle() as i32, ptr0) };
+                        let l2 = *ptr0.add(0).cast::<*mut u8>();
+                        let l3 = *ptr0
+                            .add(::core::mem::size_of::<*const u8>())
+                            .cast::<usize>();
+                        let len4 = l3;
+                        let bytes4 = _rt::Vec::from_raw_parts(l2.cast(), len4, len4);
+                        let result5 = _rt::string_lift(bytes4);
+                        result5
                     }
                 }
             }
@@ -197,28 +315,462 @@ pub mod junkyard_vfs {
                     let ptr0 = vec0.as_ptr().cast::<u8>();
                     let len0 = vec0.len();
                     #[cfg(target_arch = "wasm32")]
-                    #[link(wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host")]
-                    extern "C" {
+                    #[link(
+                        wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                    )]
+                    unsafe extern "C" {
                         #[link_name = "create-absolute-path"]
-                        fn wit_import(_: *mut u8, _: usize) -> i32;
+                        fn wit_import1(_: *mut u8, _: usize) -> i32;
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8, _: usize) -> i32 {
+                        unreachable!()
+                    }
+                    let ret = unsafe { wit_import1(ptr0.cast_mut(), len0) };
+                    unsafe { AbsolutePath::from_handle(ret as u32) }
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Writes a message to the host's log, where the user can read it
+            pub fn log(level: LogLevel, message: &str) -> () {
+                unsafe {
+                    let vec0 = message;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(
+                        wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                    )]
+                    unsafe extern "C" {
+                        #[link_name = "log"]
+                        fn wit_import1(_: i32, _: *mut u8, _: usize);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: i32, _: *mut u8, _: usize) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import1(level.clone() as i32, ptr0.cast_mut(), len0) };
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Values the plugin keeps between sessions, no other plugin can read
+            /// them
+            pub fn storage_get(key: &str) -> Option<_rt::Vec<u8>> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(
+                        wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                    )]
+                    unsafe extern "C" {
+                        #[link_name = "storage-get"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result7 = match l3 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l4 = *ptr1
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l5 = *ptr1
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len6 = l5;
+                                _rt::Vec::from_raw_parts(l4.cast(), len6, len6)
+                            };
+                            Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result7
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn storage_set(key: &str, value: &[u8]) -> Result<(), StorageError> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 4 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 4
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = value;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(
+                        wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                    )]
+                    unsafe extern "C" {
+                        #[link_name = "storage-set"]
+                        fn wit_import3(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2)
+                    };
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result10 = match l4 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l5 = i32::from(
+                                    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                let v9 = match l5 {
+                                    0 => StorageError::QuotaExceeded,
+                                    n => {
+                                        debug_assert_eq!(n, 1, "invalid enum discriminant");
+                                        let e9 = {
+                                            let l6 = *ptr2
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l7 = *ptr2
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len8 = l7;
+                                            let bytes8 = _rt::Vec::from_raw_parts(
+                                                l6.cast(),
+                                                len8,
+                                                len8,
+                                            );
+                                            _rt::string_lift(bytes8)
+                                        };
+                                        StorageError::Io(e9)
+                                    }
+                                };
+                                v9
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result10
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            pub fn storage_delete(key: &str) -> Result<(), StorageError> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 4 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 4
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = key;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let ptr1 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(
+                        wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                    )]
+                    unsafe extern "C" {
+                        #[link_name = "storage-delete"]
+                        fn wit_import2(_: *mut u8, _: usize, _: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import2(_: *mut u8, _: usize, _: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import2(ptr0.cast_mut(), len0, ptr1) };
+                    let l3 = i32::from(*ptr1.add(0).cast::<u8>());
+                    let result9 = match l3 {
+                        0 => {
+                            let e = ();
+                            Ok(e)
+                        }
+                        1 => {
+                            let e = {
+                                let l4 = i32::from(
+                                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                                );
+                                let v8 = match l4 {
+                                    0 => StorageError::QuotaExceeded,
+                                    n => {
+                                        debug_assert_eq!(n, 1, "invalid enum discriminant");
+                                        let e8 = {
+                                            let l5 = *ptr1
+                                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l6 = *ptr1
+                                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            let len7 = l6;
+                                            let bytes7 = _rt::Vec::from_raw_parts(
+                                                l5.cast(),
+                                                len7,
+                                                len7,
+                                            );
+                                            _rt::string_lift(bytes7)
+                                        };
+                                        StorageError::Io(e8)
+                                    }
+                                };
+                                v8
+                            };
+                            Err(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result9
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// The keys of the stored values, sorted
+            pub fn storage_list() -> _rt::Vec<_rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 2 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 2
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let ptr0 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(
+                        wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                    )]
+                    unsafe extern "C" {
+                        #[link_name = "storage-list"]
+                        fn wit_import1(_: *mut u8);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(_: *mut u8) {
+                        unreachable!()
+                    }
+                    unsafe { wit_import1(ptr0) };
+                    let l2 = *ptr0.add(0).cast::<*mut u8>();
+                    let l3 = *ptr0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base7 = l2;
+                    let len7 = l3;
+                    let mut result7 = _rt::Vec::with_capacity(len7);
+                    for i in 0..len7 {
+                        let base = base7
+                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                        let e7 = {
+                            let l4 = *base.add(0).cast::<*mut u8>();
+                            let l5 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len6 = l5;
+                            let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+                            _rt::string_lift(bytes6)
+                        };
+                        result7.push(e7);
+                    }
+                    _rt::cabi_dealloc(
+                        base7,
+                        len7 * (2 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result8 = result7;
+                    result8
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Asks the user for a password or another secret, none if the user
+            /// refused. The call waits for the answer, its time limit starts again
+            /// once the user answered.
+            pub fn prompt_secret(title: &str, description: &str) -> Option<_rt::String> {
+                unsafe {
+                    #[cfg_attr(target_pointer_width = "64", repr(align(8)))]
+                    #[cfg_attr(target_pointer_width = "32", repr(align(4)))]
+                    struct RetArea(
+                        [::core::mem::MaybeUninit<
+                            u8,
+                        >; 3 * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let mut ret_area = RetArea(
+                        [::core::mem::MaybeUninit::uninit(); 3
+                            * ::core::mem::size_of::<*const u8>()],
+                    );
+                    let vec0 = title;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    let vec1 = description;
+                    let ptr1 = vec1.as_ptr().cast::<u8>();
+                    let len1 = vec1.len();
+                    let ptr2 = ret_area.0.as_mut_ptr().cast::<u8>();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(
+                        wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                    )]
+                    unsafe extern "C" {
+                        #[link_name = "prompt-secret"]
+                        fn wit_import3(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                        );
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import3(
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                        _: usize,
+                        _: *mut u8,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import3(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1, ptr2)
+                    };
+                    let l4 = i32::from(*ptr2.add(0).cast::<u8>());
+                    let result8 = match l4 {
+                        0 => None,
+                        1 => {
+                            let e = {
+                                let l5 = *ptr2
+                                    .add(::core::mem::size_of::<*const u8>())
+                                    .cast::<*mut u8>();
+                                let l6 = *ptr2
+                                    .add(2 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<usize>();
+                                let len7 = l6;
+                                let bytes7 = _rt::Vec::from_raw_parts(
+                                    l5.cast(),
+                                    len7,
+                                    len7,
+                                );
+                                _rt::string_lift(bytes7)
+                            };
+                            Some(e)
+                        }
+                        _ => _rt::invalid_enum_discriminant(),
+                    };
+                    result8
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Tells the user how far the current call got, `total` is 0 if it is
+            /// not known. Every report gives the call a new time limit.
+            pub fn report_progress(done: u64, total: u64, message: &str) -> () {
+                unsafe {
+                    let vec0 = message;
+                    let ptr0 = vec0.as_ptr().cast::<u8>();
+                    let len0 = vec0.len();
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(
+                        wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                    )]
+                    unsafe extern "C" {
+                        #[link_name = "report-progress"]
+                        fn wit_import1(_: i64, _: i64, _: *mut u8, _: usize);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unsafe extern "C" fn wit_import1(
+                        _: i64,
+                        _: i64,
+                        _: *mut u8,
+                        _: usize,
+                    ) {
+                        unreachable!()
+                    }
+                    unsafe {
+                        wit_import1(
+                            _rt::as_i64(&done),
+                            _rt::as_i64(&total),
+                            ptr0.cast_mut(),
+                            len0,
+                        )
+                    };
+                }
+            }
+            #[allow(unused_unsafe, clippy::all)]
+            /// Whether the user cancelled the current call, which should then fail
+            /// with `cancelled` as soon as it can
+            pub fn is_cancelled() -> bool {
+                unsafe {
+                    #[cfg(target_arch = "wasm32")]
+                    #[link(
+                        wasm_import_module = "junkyard-vfs:vfs-plugin/vfs-host@1.0.0"
+                    )]
+                    unsafe extern "C" {
+                        #[link_name = "is-cancelled"]
+                        fn wit_import0() -> i32;
                     }
                     #[cfg(not(target_arch = "wasm32"))]
-                    fn wit_import(_: *mut u8, _: usize) -> i32 {
+                    unsafe extern "C" fn wit_import0() -> i32 {
                         unreachable!()
                     }
-                    let ret = wit_import(ptr0.cast_mut(), len0);
-                    AbsolutePath::from_handle(ret as u32)
+                    let ret = unsafe { wit_import0() };
+                    _rt::bool_lift(ret as u8)
                 }
             }
         }
     }
 }
 #[rustfmt::skip]
+#[rustfmt::skip]
 #[allow(dead_code, clippy::all)]
 pub mod exports {
     pub mod junkyard_vfs {
         pub mod vfs_plugin {
-            #[allow(dead_code, clippy::all)]
+            #[allow(dead_code, async_fn_in_trait, unused_imports, clippy::all)]
             pub mod vfs {
                 #[used]
                 #[doc(hidden)]
@@ -283,11 +835,43 @@ pub mod exports {
                         }
                     }
                 }
+                /// A point in time, as seconds and nanoseconds since the unix epoch
                 #[repr(C)]
                 #[derive(Clone, Copy)]
+                pub struct Datetime {
+                    pub seconds: i64,
+                    pub nanoseconds: u32,
+                }
+                impl ::core::fmt::Debug for Datetime {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("Datetime")
+                            .field("seconds", &self.seconds)
+                            .field("nanoseconds", &self.nanoseconds)
+                            .finish()
+                    }
+                }
+                /// File metadata, fields that a filesystem cannot provide are left empty
+                #[derive(Clone)]
                 pub struct Stat {
                     pub kind: Kind,
                     pub size: u64,
+                    pub modified: Option<Datetime>,
+                    pub accessed: Option<Datetime>,
+                    pub created: Option<Datetime>,
+                    /// unix permission and file type bits
+                    pub mode: Option<u32>,
+                    pub uid: Option<u32>,
+                    pub gid: Option<u32>,
+                    /// name of the owner, if it can be resolved
+                    pub user: Option<_rt::String>,
+                    /// name of the owning group, if it can be resolved
+                    pub group: Option<_rt::String>,
+                    pub inode: Option<u64>,
+                    pub device: Option<u64>,
+                    pub nlink: Option<u64>,
                 }
                 impl ::core::fmt::Debug for Stat {
                     fn fmt(
@@ -297,6 +881,215 @@ pub mod exports {
                         f.debug_struct("Stat")
                             .field("kind", &self.kind)
                             .field("size", &self.size)
+                            .field("modified", &self.modified)
+                            .field("accessed", &self.accessed)
+                            .field("created", &self.created)
+                            .field("mode", &self.mode)
+                            .field("uid", &self.uid)
+                            .field("gid", &self.gid)
+                            .field("user", &self.user)
+                            .field("group", &self.group)
+                            .field("inode", &self.inode)
+                            .field("device", &self.device)
+                            .field("nlink", &self.nlink)
+                            .finish()
+                    }
+                }
+                #[derive(Clone)]
+                pub enum VfsError {
+                    NotFound,
+                    PermissionDenied,
+                    AlreadyExists,
+                    NotADirectory,
+                    IsADirectory,
+                    NotEmpty,
+                    InvalidPath(_rt::String),
+                    Unsupported,
+                    Io(_rt::String),
+                    PluginTrap(_rt::String),
+                    /// the plugin did not finish the call in the allowed time
+                    Timeout,
+                    /// the plugin tried to use more memory or resources than allowed
+                    LimitExceeded(_rt::String),
+                    /// the plugin answered with data that the host refused
+                    InvalidResponse(_rt::String),
+                    /// the user cancelled the operation
+                    Cancelled,
+                }
+                impl ::core::fmt::Debug for VfsError {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            VfsError::NotFound => {
+                                f.debug_tuple("VfsError::NotFound").finish()
+                            }
+                            VfsError::PermissionDenied => {
+                                f.debug_tuple("VfsError::PermissionDenied").finish()
+                            }
+                            VfsError::AlreadyExists => {
+                                f.debug_tuple("VfsError::AlreadyExists").finish()
+                            }
+                            VfsError::NotADirectory => {
+                                f.debug_tuple("VfsError::NotADirectory").finish()
+                            }
+                            VfsError::IsADirectory => {
+                                f.debug_tuple("VfsError::IsADirectory").finish()
+                            }
+                            VfsError::NotEmpty => {
+                                f.debug_tuple("VfsError::NotEmpty").finish()
+                            }
+                            VfsError::InvalidPath(e) => {
+                                f.debug_tuple("VfsError::InvalidPath").field(e).finish()
+                            }
+                            VfsError::Unsupported => {
+                                f.debug_tuple("VfsError::Unsupported").finish()
+                            }
+                            VfsError::Io(e) => {
+                                f.debug_tuple("VfsError::Io").field(e).finish()
+                            }
+                            VfsError::PluginTrap(e) => {
+                                f.debug_tuple("VfsError::PluginTrap").field(e).finish()
+                            }
+                            VfsError::Timeout => {
+                                f.debug_tuple("VfsError::Timeout").finish()
+                            }
+                            VfsError::LimitExceeded(e) => {
+                                f.debug_tuple("VfsError::LimitExceeded").field(e).finish()
+                            }
+                            VfsError::InvalidResponse(e) => {
+                                f.debug_tuple("VfsError::InvalidResponse").field(e).finish()
+                            }
+                            VfsError::Cancelled => {
+                                f.debug_tuple("VfsError::Cancelled").finish()
+                            }
+                        }
+                    }
+                }
+                impl ::core::fmt::Display for VfsError {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        write!(f, "{:?}", self)
+                    }
+                }
+                impl std::error::Error for VfsError {}
+                /// A folder entry together with its metadata, links are not followed
+                #[derive(Clone)]
+                pub struct DirEntry {
+                    pub name: _rt::String,
+                    pub stat: Stat,
+                }
+                impl ::core::fmt::Debug for DirEntry {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("DirEntry")
+                            .field("name", &self.name)
+                            .field("stat", &self.stat)
+                            .finish()
+                    }
+                }
+                wit_bindgen_rt::bitflags::bitflags! {
+                    #[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy)]
+                    pub struct OpenFlags : u8 { const CREATE = 1 << 0; const READ = 1 <<
+                    1; const WRITE = 1 << 2; const TRUNCATE = 1 << 3; const APPEND = 1 <<
+                    4; }
+                }
+                /// Something a plugin supports besides listing folders and reading
+                /// metadata
+                #[repr(u8)]
+                #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+                pub enum Capability {
+                    Read,
+                    Write,
+                    Links,
+                    Rename,
+                }
+                impl ::core::fmt::Debug for Capability {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        match self {
+                            Capability::Read => {
+                                f.debug_tuple("Capability::Read").finish()
+                            }
+                            Capability::Write => {
+                                f.debug_tuple("Capability::Write").finish()
+                            }
+                            Capability::Links => {
+                                f.debug_tuple("Capability::Links").finish()
+                            }
+                            Capability::Rename => {
+                                f.debug_tuple("Capability::Rename").finish()
+                            }
+                        }
+                    }
+                }
+                impl Capability {
+                    #[doc(hidden)]
+                    pub unsafe fn _lift(val: u8) -> Capability {
+                        if !cfg!(debug_assertions) {
+                            return ::core::mem::transmute(val);
+                        }
+                        match val {
+                            0 => Capability::Read,
+                            1 => Capability::Write,
+                            2 => Capability::Links,
+                            3 => Capability::Rename,
+                            _ => panic!("invalid enum discriminant"),
+                        }
+                    }
+                }
+                /// What a plugin says about itself
+                #[derive(Clone)]
+                pub struct PluginInfo {
+                    pub name: _rt::String,
+                    pub version: _rt::String,
+                    pub author: _rt::String,
+                    /// the URI schemes of the paths the plugin serves, like `file` or `s3`
+                    pub schemes: _rt::Vec<_rt::String>,
+                    pub capabilities: _rt::Vec<Capability>,
+                    /// a JSON schema of the configuration the plugin accepts, empty if
+                    /// it does not take any
+                    pub config_schema: _rt::String,
+                }
+                impl ::core::fmt::Debug for PluginInfo {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("PluginInfo")
+                            .field("name", &self.name)
+                            .field("version", &self.version)
+                            .field("author", &self.author)
+                            .field("schemes", &self.schemes)
+                            .field("capabilities", &self.capabilities)
+                            .field("config-schema", &self.config_schema)
+                            .finish()
+                    }
+                }
+                /// What a filesystem is mounted on
+                #[derive(Clone)]
+                pub struct MountConfig {
+                    /// the source of the filesystem, like `file:///home/user` or
+                    /// `s3://bucket`
+                    pub uri: _rt::String,
+                    /// settings described by the config schema of the plugin
+                    pub options: _rt::Vec<(_rt::String, _rt::String)>,
+                }
+                impl ::core::fmt::Debug for MountConfig {
+                    fn fmt(
+                        &self,
+                        f: &mut ::core::fmt::Formatter<'_>,
+                    ) -> ::core::fmt::Result {
+                        f.debug_struct("MountConfig")
+                            .field("uri", &self.uri)
+                            .field("options", &self.options)
                             .finish()
                     }
                 }
@@ -339,7 +1132,7 @@ pub mod exports {
                     #[doc(hidden)]
                     pub unsafe fn from_handle(handle: u32) -> Self {
                         Self {
-                            handle: _rt::Resource::from_handle(handle),
+                            handle: unsafe { _rt::Resource::from_handle(handle) },
                         }
                     }
                     #[doc(hidden)]
@@ -370,7 +1163,9 @@ pub mod exports {
                     #[doc(hidden)]
                     pub unsafe fn dtor<T: 'static>(handle: *mut u8) {
                         Self::type_guard::<T>();
-                        let _ = _rt::Box::from_raw(handle as *mut _FileRep<T>);
+                        let _ = unsafe {
+                            _rt::Box::from_raw(handle as *mut _FileRep<T>)
+                        };
                     }
                     fn as_ptr<T: GuestFile>(&self) -> *mut _FileRep<T> {
                         File::type_guard::<T>();
@@ -411,13 +1206,13 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs"
+                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs@1.0.0"
                             )]
-                            extern "C" {
+                            unsafe extern "C" {
                                 #[link_name = "[resource-drop]file"]
                                 fn drop(_: u32);
                             }
-                            drop(_handle);
+                            unsafe { drop(_handle) };
                         }
                     }
                 }
@@ -460,7 +1255,7 @@ pub mod exports {
                     #[doc(hidden)]
                     pub unsafe fn from_handle(handle: u32) -> Self {
                         Self {
-                            handle: _rt::Resource::from_handle(handle),
+                            handle: unsafe { _rt::Resource::from_handle(handle) },
                         }
                     }
                     #[doc(hidden)]
@@ -491,7 +1286,9 @@ pub mod exports {
                     #[doc(hidden)]
                     pub unsafe fn dtor<T: 'static>(handle: *mut u8) {
                         Self::type_guard::<T>();
-                        let _ = _rt::Box::from_raw(handle as *mut _FilesystemRep<T>);
+                        let _ = unsafe {
+                            _rt::Box::from_raw(handle as *mut _FilesystemRep<T>)
+                        };
                     }
                     fn as_ptr<T: GuestFilesystem>(&self) -> *mut _FilesystemRep<T> {
                         Filesystem::type_guard::<T>();
@@ -532,13 +1329,13 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs"
+                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs@1.0.0"
                             )]
-                            extern "C" {
+                            unsafe extern "C" {
                                 #[link_name = "[resource-drop]filesystem"]
                                 fn drop(_: u32);
                             }
-                            drop(_handle);
+                            unsafe { drop(_handle) };
                         }
                     }
                 }
@@ -546,32 +1343,155 @@ pub mod exports {
                 #[allow(non_snake_case)]
                 pub unsafe fn _export_method_file_read_cabi<T: GuestFile>(
                     arg0: *mut u8,
-                    arg1: *mut u8,
-                    arg2: usize,
+                    arg1: i64,
                 ) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let len0 = arg2;
-                    let result1 = T::read(
-                        FileBorrow::lift(arg0 as u32 as usize).get(),
-                        _rt::Vec::from_raw_parts(arg1.cast(), len0, len0),
+                    let result0 = T::read(
+                        unsafe { FileBorrow::lift(arg0 as u32 as usize) }.get(),
+                        arg1 as u64,
                     );
-                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
-                    match result1 {
+                    let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result0 {
                         Ok(e) => {
-                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
-                            *ptr2.add(8).cast::<i64>() = _rt::as_i64(e);
+                            *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec2 = (e).into_boxed_slice();
+                            let ptr2 = vec2.as_ptr().cast::<u8>();
+                            let len2 = vec2.len();
+                            ::core::mem::forget(vec2);
+                            *ptr1
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len2;
+                            *ptr1
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr2.cast_mut();
                         }
                         Err(e) => {
-                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
-                            let vec3 = (e.into_bytes()).into_boxed_slice();
-                            let ptr3 = vec3.as_ptr().cast::<u8>();
-                            let len3 = vec3.len();
-                            ::core::mem::forget(vec3);
-                            *ptr2.add(12).cast::<usize>() = len3;
-                            *ptr2.add(8).cast::<*mut u8>() = ptr3.cast_mut();
-                        }
-                    };
-                    ptr2
+                            *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec3 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                                    let len3 = vec3.len();
+                                    ::core::mem::forget(vec3);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len3;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr3.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr1
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr1
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr1
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr1
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
@@ -580,11 +1500,77 @@ pub mod exports {
                 ) {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
-                        0 => {}
+                        0 => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base3 = l1;
+                            let len3 = l2;
+                            _rt::cabi_dealloc(base3, len3 * 1, 1);
+                        }
                         _ => {
-                            let l1 = *arg0.add(8).cast::<*mut u8>();
-                            let l2 = *arg0.add(12).cast::<usize>();
-                            _rt::cabi_dealloc(l1, l2, 1);
+                            let l4 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l4 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l5 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l5, l6, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l7 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l8 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l7, l8, 1);
+                                }
+                                9 => {
+                                    let l9 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l10 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l9, l10, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l11 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l12 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l11, l12, 1);
+                                }
+                                12 => {
+                                    let l13 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l14 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l13, l14, 1);
+                                }
+                                _ => {}
+                            }
                         }
                     }
                 }
@@ -598,10 +1584,10 @@ pub mod exports {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
                     let len0 = arg2;
                     let result1 = T::write(
-                        FileBorrow::lift(arg0 as u32 as usize).get(),
+                        unsafe { FileBorrow::lift(arg0 as u32 as usize) }.get(),
                         _rt::Vec::from_raw_parts(arg1.cast(), len0, len0),
                     );
-                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result1 {
                         Ok(e) => {
                             *ptr2.add(0).cast::<u8>() = (0i32) as u8;
@@ -609,12 +1595,100 @@ pub mod exports {
                         }
                         Err(e) => {
                             *ptr2.add(0).cast::<u8>() = (1i32) as u8;
-                            let vec3 = (e.into_bytes()).into_boxed_slice();
-                            let ptr3 = vec3.as_ptr().cast::<u8>();
-                            let len3 = vec3.len();
-                            ::core::mem::forget(vec3);
-                            *ptr2.add(12).cast::<usize>() = len3;
-                            *ptr2.add(8).cast::<*mut u8>() = ptr3.cast_mut();
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2.add(8).cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2.add(8).cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2.add(8).cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2.add(8).cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2.add(8).cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2.add(8).cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (6i32) as u8;
+                                    let vec3 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                                    let len3 = vec3.len();
+                                    ::core::mem::forget(vec3);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len3;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr3.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2.add(8).cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (8i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (9i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2.add(8).cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (11i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (12i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2.add(8).cast::<u8>() = (13i32) as u8;
+                                }
+                            }
                         }
                     };
                     ptr2
@@ -628,9 +1702,63 @@ pub mod exports {
                     match l0 {
                         0 => {}
                         _ => {
-                            let l1 = *arg0.add(8).cast::<*mut u8>();
-                            let l2 = *arg0.add(12).cast::<usize>();
-                            _rt::cabi_dealloc(l1, l2, 1);
+                            let l1 = i32::from(*arg0.add(8).cast::<u8>());
+                            match l1 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l2 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l4 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                9 => {
+                                    let l6 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l8 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                12 => {
+                                    let l10 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                _ => {}
+                            }
                         }
                     }
                 }
@@ -658,238 +1786,3792 @@ pub mod exports {
                         }
                     };
                     let result1 = T::seek(
-                        FileBorrow::lift(arg0 as u32 as usize).get(),
+                        unsafe { FileBorrow::lift(arg0 as u32 as usize) }.get(),
                         v0,
                     );
-                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            *ptr2.add(8).cast::<i64>() = _rt::as_i64(e);
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2.add(8).cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2.add(8).cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2.add(8).cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2.add(8).cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2.add(8).cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2.add(8).cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (6i32) as u8;
+                                    let vec3 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                                    let len3 = vec3.len();
+                                    ::core::mem::forget(vec3);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len3;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr3.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2.add(8).cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (8i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (9i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2.add(8).cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (11i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (12i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2.add(8).cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_file_seek<T: GuestFile>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = i32::from(*arg0.add(8).cast::<u8>());
+                            match l1 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l2 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l4 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                9 => {
+                                    let l6 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l8 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                12 => {
+                                    let l10 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_filesystem_read_dir_cabi<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8, arg1: i32) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let handle0;
+                    let result1 = T::read_dir(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
+                        {
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
+                            &handle0
+                        },
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec3 = e;
+                            let len3 = vec3.len();
+                            let layout3 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec3.len() * 4,
+                                4,
+                            );
+                            let result3 = if layout3.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout3).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout3);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec3.into_iter().enumerate() {
+                                let base = result3.add(i * 4);
+                                {
+                                    *base.add(0).cast::<i32>() = (e).take_handle() as i32;
+                                }
+                            }
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result3;
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec8 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_filesystem_read_dir<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base3 = l1;
+                            let len3 = l2;
+                            _rt::cabi_dealloc(base3, len3 * 4, 4);
+                        }
+                        _ => {
+                            let l4 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l4 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l5 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l5, l6, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l7 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l8 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l7, l8, 1);
+                                }
+                                9 => {
+                                    let l9 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l10 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l9, l10, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l11 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l12 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l11, l12, 1);
+                                }
+                                12 => {
+                                    let l13 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l14 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l13, l14, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_filesystem_read_dir_with_stat_cabi<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8, arg1: i32) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let handle0;
+                    let result1 = T::read_dir_with_stat(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
+                        {
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
+                            &handle0
+                        },
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec11 = e;
+                            let len11 = vec11.len();
+                            let layout11 = _rt::alloc::Layout::from_size_align_unchecked(
+                                vec11.len()
+                                    * (160 + 8 * ::core::mem::size_of::<*const u8>()),
+                                8,
+                            );
+                            let result11 = if layout11.size() != 0 {
+                                let ptr = _rt::alloc::alloc(layout11).cast::<u8>();
+                                if ptr.is_null() {
+                                    _rt::alloc::handle_alloc_error(layout11);
+                                }
+                                ptr
+                            } else {
+                                ::core::ptr::null_mut()
+                            };
+                            for (i, e) in vec11.into_iter().enumerate() {
+                                let base = result11
+                                    .add(i * (160 + 8 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let DirEntry { name: name3, stat: stat3 } = e;
+                                    let vec4 = (name3.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *base.add(0).cast::<*mut u8>() = ptr4.cast_mut();
+                                    let Stat {
+                                        kind: kind5,
+                                        size: size5,
+                                        modified: modified5,
+                                        accessed: accessed5,
+                                        created: created5,
+                                        mode: mode5,
+                                        uid: uid5,
+                                        gid: gid5,
+                                        user: user5,
+                                        group: group5,
+                                        inode: inode5,
+                                        device: device5,
+                                        nlink: nlink5,
+                                    } = stat3;
+                                    *base
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (kind5.clone() as i32) as u8;
+                                    *base
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<i64>() = _rt::as_i64(size5);
+                                    match modified5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let Datetime {
+                                                seconds: seconds6,
+                                                nanoseconds: nanoseconds6,
+                                            } = e;
+                                            *base
+                                                .add(24 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i64>() = _rt::as_i64(seconds6);
+                                            *base
+                                                .add(32 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(nanoseconds6);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(16 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match accessed5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(40 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let Datetime {
+                                                seconds: seconds7,
+                                                nanoseconds: nanoseconds7,
+                                            } = e;
+                                            *base
+                                                .add(48 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i64>() = _rt::as_i64(seconds7);
+                                            *base
+                                                .add(56 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(nanoseconds7);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(40 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match created5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(64 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let Datetime {
+                                                seconds: seconds8,
+                                                nanoseconds: nanoseconds8,
+                                            } = e;
+                                            *base
+                                                .add(72 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i64>() = _rt::as_i64(seconds8);
+                                            *base
+                                                .add(80 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(nanoseconds8);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(64 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match mode5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(88 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(92 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(88 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match uid5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(96 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(100 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(96 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match gid5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(104 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(108 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i32>() = _rt::as_i32(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(104 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match user5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(112 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let vec9 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr9 = vec9.as_ptr().cast::<u8>();
+                                            let len9 = vec9.len();
+                                            ::core::mem::forget(vec9);
+                                            *base
+                                                .add(112 + 4 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len9;
+                                            *base
+                                                .add(112 + 3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr9.cast_mut();
+                                        }
+                                        None => {
+                                            *base
+                                                .add(112 + 2 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match group5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(112 + 5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            let vec10 = (e.into_bytes()).into_boxed_slice();
+                                            let ptr10 = vec10.as_ptr().cast::<u8>();
+                                            let len10 = vec10.len();
+                                            ::core::mem::forget(vec10);
+                                            *base
+                                                .add(112 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>() = len10;
+                                            *base
+                                                .add(112 + 6 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>() = ptr10.cast_mut();
+                                        }
+                                        None => {
+                                            *base
+                                                .add(112 + 5 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match inode5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(112 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(120 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i64>() = _rt::as_i64(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(112 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match device5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(128 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(136 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i64>() = _rt::as_i64(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(128 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                    match nlink5 {
+                                        Some(e) => {
+                                            *base
+                                                .add(144 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (1i32) as u8;
+                                            *base
+                                                .add(152 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<i64>() = _rt::as_i64(e);
+                                        }
+                                        None => {
+                                            *base
+                                                .add(144 + 8 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<u8>() = (0i32) as u8;
+                                        }
+                                    };
+                                }
+                            }
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len11;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = result11;
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec12 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr12 = vec12.as_ptr().cast::<u8>();
+                                    let len12 = vec12.len();
+                                    ::core::mem::forget(vec12);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len12;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr12.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec13 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr13 = vec13.as_ptr().cast::<u8>();
+                                    let len13 = vec13.len();
+                                    ::core::mem::forget(vec13);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec14 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr14 = vec14.as_ptr().cast::<u8>();
+                                    let len14 = vec14.len();
+                                    ::core::mem::forget(vec14);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len14;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr14.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec15 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr15 = vec15.as_ptr().cast::<u8>();
+                                    let len15 = vec15.len();
+                                    ::core::mem::forget(vec15);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len15;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr15.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec16 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr16 = vec16.as_ptr().cast::<u8>();
+                                    let len16 = vec16.len();
+                                    ::core::mem::forget(vec16);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len16;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr16.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_filesystem_read_dir_with_stat<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base11 = l1;
+                            let len11 = l2;
+                            for i in 0..len11 {
+                                let base = base11
+                                    .add(i * (160 + 8 * ::core::mem::size_of::<*const u8>()));
+                                {
+                                    let l3 = *base.add(0).cast::<*mut u8>();
+                                    let l4 = *base
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l3, l4, 1);
+                                    let l5 = i32::from(
+                                        *base
+                                            .add(112 + 2 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l5 {
+                                        0 => {}
+                                        _ => {
+                                            let l6 = *base
+                                                .add(112 + 3 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l7 = *base
+                                                .add(112 + 4 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l6, l7, 1);
+                                        }
+                                    }
+                                    let l8 = i32::from(
+                                        *base
+                                            .add(112 + 5 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<u8>(),
+                                    );
+                                    match l8 {
+                                        0 => {}
+                                        _ => {
+                                            let l9 = *base
+                                                .add(112 + 6 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<*mut u8>();
+                                            let l10 = *base
+                                                .add(112 + 7 * ::core::mem::size_of::<*const u8>())
+                                                .cast::<usize>();
+                                            _rt::cabi_dealloc(l9, l10, 1);
+                                        }
+                                    }
+                                }
+                            }
+                            _rt::cabi_dealloc(
+                                base11,
+                                len11 * (160 + 8 * ::core::mem::size_of::<*const u8>()),
+                                8,
+                            );
+                        }
+                        _ => {
+                            let l12 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l12 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l13 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l14 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l13, l14, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l15 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l16 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l15, l16, 1);
+                                }
+                                9 => {
+                                    let l17 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l18 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l17, l18, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l19 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l20 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l19, l20, 1);
+                                }
+                                12 => {
+                                    let l21 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l22 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l21, l22, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_filesystem_stat_cabi<T: GuestFilesystem>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let handle0;
+                    let result1 = T::stat(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
+                        {
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
+                            &handle0
+                        },
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            let Stat {
+                                kind: kind3,
+                                size: size3,
+                                modified: modified3,
+                                accessed: accessed3,
+                                created: created3,
+                                mode: mode3,
+                                uid: uid3,
+                                gid: gid3,
+                                user: user3,
+                                group: group3,
+                                inode: inode3,
+                                device: device3,
+                                nlink: nlink3,
+                            } = e;
+                            *ptr2.add(8).cast::<u8>() = (kind3.clone() as i32) as u8;
+                            *ptr2.add(16).cast::<i64>() = _rt::as_i64(size3);
+                            match modified3 {
+                                Some(e) => {
+                                    *ptr2.add(24).cast::<u8>() = (1i32) as u8;
+                                    let Datetime {
+                                        seconds: seconds4,
+                                        nanoseconds: nanoseconds4,
+                                    } = e;
+                                    *ptr2.add(32).cast::<i64>() = _rt::as_i64(seconds4);
+                                    *ptr2.add(40).cast::<i32>() = _rt::as_i32(nanoseconds4);
+                                }
+                                None => {
+                                    *ptr2.add(24).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match accessed3 {
+                                Some(e) => {
+                                    *ptr2.add(48).cast::<u8>() = (1i32) as u8;
+                                    let Datetime {
+                                        seconds: seconds5,
+                                        nanoseconds: nanoseconds5,
+                                    } = e;
+                                    *ptr2.add(56).cast::<i64>() = _rt::as_i64(seconds5);
+                                    *ptr2.add(64).cast::<i32>() = _rt::as_i32(nanoseconds5);
+                                }
+                                None => {
+                                    *ptr2.add(48).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match created3 {
+                                Some(e) => {
+                                    *ptr2.add(72).cast::<u8>() = (1i32) as u8;
+                                    let Datetime {
+                                        seconds: seconds6,
+                                        nanoseconds: nanoseconds6,
+                                    } = e;
+                                    *ptr2.add(80).cast::<i64>() = _rt::as_i64(seconds6);
+                                    *ptr2.add(88).cast::<i32>() = _rt::as_i32(nanoseconds6);
+                                }
+                                None => {
+                                    *ptr2.add(72).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match mode3 {
+                                Some(e) => {
+                                    *ptr2.add(96).cast::<u8>() = (1i32) as u8;
+                                    *ptr2.add(100).cast::<i32>() = _rt::as_i32(e);
+                                }
+                                None => {
+                                    *ptr2.add(96).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match uid3 {
+                                Some(e) => {
+                                    *ptr2.add(104).cast::<u8>() = (1i32) as u8;
+                                    *ptr2.add(108).cast::<i32>() = _rt::as_i32(e);
+                                }
+                                None => {
+                                    *ptr2.add(104).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match gid3 {
+                                Some(e) => {
+                                    *ptr2.add(112).cast::<u8>() = (1i32) as u8;
+                                    *ptr2.add(116).cast::<i32>() = _rt::as_i32(e);
+                                }
+                                None => {
+                                    *ptr2.add(112).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match user3 {
+                                Some(e) => {
+                                    *ptr2.add(120).cast::<u8>() = (1i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(120 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(120 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                None => {
+                                    *ptr2.add(120).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match group3 {
+                                Some(e) => {
+                                    *ptr2
+                                        .add(120 + 3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec8 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *ptr2
+                                        .add(120 + 5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *ptr2
+                                        .add(120 + 4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                }
+                                None => {
+                                    *ptr2
+                                        .add(120 + 3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match inode3 {
+                                Some(e) => {
+                                    *ptr2
+                                        .add(120 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    *ptr2
+                                        .add(128 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<i64>() = _rt::as_i64(e);
+                                }
+                                None => {
+                                    *ptr2
+                                        .add(120 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match device3 {
+                                Some(e) => {
+                                    *ptr2
+                                        .add(136 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    *ptr2
+                                        .add(144 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<i64>() = _rt::as_i64(e);
+                                }
+                                None => {
+                                    *ptr2
+                                        .add(136 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match nlink3 {
+                                Some(e) => {
+                                    *ptr2
+                                        .add(152 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    *ptr2
+                                        .add(160 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<i64>() = _rt::as_i64(e);
+                                }
+                                None => {
+                                    *ptr2
+                                        .add(152 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2.add(8).cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2.add(8).cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2.add(8).cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2.add(8).cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2.add(8).cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2.add(8).cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (6i32) as u8;
+                                    let vec9 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr9 = vec9.as_ptr().cast::<u8>();
+                                    let len9 = vec9.len();
+                                    ::core::mem::forget(vec9);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len9;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr9.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2.add(8).cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (8i32) as u8;
+                                    let vec10 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                                    let len10 = vec10.len();
+                                    ::core::mem::forget(vec10);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len10;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr10.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (9i32) as u8;
+                                    let vec11 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr11 = vec11.as_ptr().cast::<u8>();
+                                    let len11 = vec11.len();
+                                    ::core::mem::forget(vec11);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len11;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr11.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2.add(8).cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (11i32) as u8;
+                                    let vec12 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr12 = vec12.as_ptr().cast::<u8>();
+                                    let len12 = vec12.len();
+                                    ::core::mem::forget(vec12);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len12;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr12.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (12i32) as u8;
+                                    let vec13 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr13 = vec13.as_ptr().cast::<u8>();
+                                    let len13 = vec13.len();
+                                    ::core::mem::forget(vec13);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2.add(8).cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_filesystem_stat<T: GuestFilesystem>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = i32::from(*arg0.add(120).cast::<u8>());
+                            match l1 {
+                                0 => {}
+                                _ => {
+                                    let l2 = *arg0
+                                        .add(120 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(120 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                            }
+                            let l4 = i32::from(
+                                *arg0
+                                    .add(120 + 3 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<u8>(),
+                            );
+                            match l4 {
+                                0 => {}
+                                _ => {
+                                    let l5 = *arg0
+                                        .add(120 + 4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *arg0
+                                        .add(120 + 5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l5, l6, 1);
+                                }
+                            }
+                        }
+                        _ => {
+                            let l7 = i32::from(*arg0.add(8).cast::<u8>());
+                            match l7 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l8 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l10 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                9 => {
+                                    let l12 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l13 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l12, l13, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l14 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l15 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l14, l15, 1);
+                                }
+                                12 => {
+                                    let l16 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l17 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l16, l17, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_filesystem_lstat_cabi<T: GuestFilesystem>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let handle0;
+                    let result1 = T::lstat(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
+                        {
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
+                            &handle0
+                        },
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            let Stat {
+                                kind: kind3,
+                                size: size3,
+                                modified: modified3,
+                                accessed: accessed3,
+                                created: created3,
+                                mode: mode3,
+                                uid: uid3,
+                                gid: gid3,
+                                user: user3,
+                                group: group3,
+                                inode: inode3,
+                                device: device3,
+                                nlink: nlink3,
+                            } = e;
+                            *ptr2.add(8).cast::<u8>() = (kind3.clone() as i32) as u8;
+                            *ptr2.add(16).cast::<i64>() = _rt::as_i64(size3);
+                            match modified3 {
+                                Some(e) => {
+                                    *ptr2.add(24).cast::<u8>() = (1i32) as u8;
+                                    let Datetime {
+                                        seconds: seconds4,
+                                        nanoseconds: nanoseconds4,
+                                    } = e;
+                                    *ptr2.add(32).cast::<i64>() = _rt::as_i64(seconds4);
+                                    *ptr2.add(40).cast::<i32>() = _rt::as_i32(nanoseconds4);
+                                }
+                                None => {
+                                    *ptr2.add(24).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match accessed3 {
+                                Some(e) => {
+                                    *ptr2.add(48).cast::<u8>() = (1i32) as u8;
+                                    let Datetime {
+                                        seconds: seconds5,
+                                        nanoseconds: nanoseconds5,
+                                    } = e;
+                                    *ptr2.add(56).cast::<i64>() = _rt::as_i64(seconds5);
+                                    *ptr2.add(64).cast::<i32>() = _rt::as_i32(nanoseconds5);
+                                }
+                                None => {
+                                    *ptr2.add(48).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match created3 {
+                                Some(e) => {
+                                    *ptr2.add(72).cast::<u8>() = (1i32) as u8;
+                                    let Datetime {
+                                        seconds: seconds6,
+                                        nanoseconds: nanoseconds6,
+                                    } = e;
+                                    *ptr2.add(80).cast::<i64>() = _rt::as_i64(seconds6);
+                                    *ptr2.add(88).cast::<i32>() = _rt::as_i32(nanoseconds6);
+                                }
+                                None => {
+                                    *ptr2.add(72).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match mode3 {
+                                Some(e) => {
+                                    *ptr2.add(96).cast::<u8>() = (1i32) as u8;
+                                    *ptr2.add(100).cast::<i32>() = _rt::as_i32(e);
+                                }
+                                None => {
+                                    *ptr2.add(96).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match uid3 {
+                                Some(e) => {
+                                    *ptr2.add(104).cast::<u8>() = (1i32) as u8;
+                                    *ptr2.add(108).cast::<i32>() = _rt::as_i32(e);
+                                }
+                                None => {
+                                    *ptr2.add(104).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match gid3 {
+                                Some(e) => {
+                                    *ptr2.add(112).cast::<u8>() = (1i32) as u8;
+                                    *ptr2.add(116).cast::<i32>() = _rt::as_i32(e);
+                                }
+                                None => {
+                                    *ptr2.add(112).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match user3 {
+                                Some(e) => {
+                                    *ptr2.add(120).cast::<u8>() = (1i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(120 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(120 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                None => {
+                                    *ptr2.add(120).cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match group3 {
+                                Some(e) => {
+                                    *ptr2
+                                        .add(120 + 3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec8 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *ptr2
+                                        .add(120 + 5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *ptr2
+                                        .add(120 + 4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                }
+                                None => {
+                                    *ptr2
+                                        .add(120 + 3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match inode3 {
+                                Some(e) => {
+                                    *ptr2
+                                        .add(120 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    *ptr2
+                                        .add(128 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<i64>() = _rt::as_i64(e);
+                                }
+                                None => {
+                                    *ptr2
+                                        .add(120 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match device3 {
+                                Some(e) => {
+                                    *ptr2
+                                        .add(136 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    *ptr2
+                                        .add(144 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<i64>() = _rt::as_i64(e);
+                                }
+                                None => {
+                                    *ptr2
+                                        .add(136 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                            match nlink3 {
+                                Some(e) => {
+                                    *ptr2
+                                        .add(152 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    *ptr2
+                                        .add(160 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<i64>() = _rt::as_i64(e);
+                                }
+                                None => {
+                                    *ptr2
+                                        .add(152 + 6 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2.add(8).cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2.add(8).cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2.add(8).cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2.add(8).cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2.add(8).cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2.add(8).cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (6i32) as u8;
+                                    let vec9 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr9 = vec9.as_ptr().cast::<u8>();
+                                    let len9 = vec9.len();
+                                    ::core::mem::forget(vec9);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len9;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr9.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2.add(8).cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (8i32) as u8;
+                                    let vec10 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                                    let len10 = vec10.len();
+                                    ::core::mem::forget(vec10);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len10;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr10.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (9i32) as u8;
+                                    let vec11 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr11 = vec11.as_ptr().cast::<u8>();
+                                    let len11 = vec11.len();
+                                    ::core::mem::forget(vec11);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len11;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr11.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2.add(8).cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (11i32) as u8;
+                                    let vec12 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr12 = vec12.as_ptr().cast::<u8>();
+                                    let len12 = vec12.len();
+                                    ::core::mem::forget(vec12);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len12;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr12.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2.add(8).cast::<u8>() = (12i32) as u8;
+                                    let vec13 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr13 = vec13.as_ptr().cast::<u8>();
+                                    let len13 = vec13.len();
+                                    ::core::mem::forget(vec13);
+                                    *ptr2
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *ptr2
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2.add(8).cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_filesystem_lstat<T: GuestFilesystem>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = i32::from(*arg0.add(120).cast::<u8>());
+                            match l1 {
+                                0 => {}
+                                _ => {
+                                    let l2 = *arg0
+                                        .add(120 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(120 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                            }
+                            let l4 = i32::from(
+                                *arg0
+                                    .add(120 + 3 * ::core::mem::size_of::<*const u8>())
+                                    .cast::<u8>(),
+                            );
+                            match l4 {
+                                0 => {}
+                                _ => {
+                                    let l5 = *arg0
+                                        .add(120 + 4 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l6 = *arg0
+                                        .add(120 + 5 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l5, l6, 1);
+                                }
+                            }
+                        }
+                        _ => {
+                            let l7 = i32::from(*arg0.add(8).cast::<u8>());
+                            match l7 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l8 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l10 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                9 => {
+                                    let l12 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l13 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l12, l13, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l14 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l15 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l14, l15, 1);
+                                }
+                                12 => {
+                                    let l16 = *arg0
+                                        .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l17 = *arg0
+                                        .add(8 + 2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l16, l17, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_filesystem_read_link_cabi<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8, arg1: i32) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let handle0;
+                    let result1 = T::read_link(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
+                        {
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
+                            &handle0
+                        },
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec3 = (e.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec8 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_filesystem_read_link<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l1, l2, 1);
+                        }
+                        _ => {
+                            let l3 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l3 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l4 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l6 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                9 => {
+                                    let l8 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                12 => {
+                                    let l12 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l13 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l12, l13, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_filesystem_create_symlink_cabi<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8, arg1: *mut u8, arg2: usize, arg3: i32) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let handle1;
+                    let len0 = arg2;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg1.cast(), len0, len0);
+                    let result2 = T::create_symlink(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
+                        _rt::string_lift(bytes0),
+                        {
+                            handle1 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg3 as u32,
+                                )
+                            };
+                            &handle1
+                        },
+                    );
+                    let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result2 {
+                        Ok(_) => {
+                            *ptr3.add(0).cast::<u8>() = (0i32) as u8;
+                        }
+                        Err(e) => {
+                            *ptr3.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec8 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr3
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_filesystem_create_symlink<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l4 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                9 => {
+                                    let l6 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l8 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                12 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_filesystem_open_cabi<T: GuestFilesystem>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                    arg2: i32,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let handle0;
+                    let result1 = T::open(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
+                        {
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
+                            &handle0
+                        },
+                        OpenFlags::empty()
+                            | OpenFlags::from_bits_retain(((arg2 as u8) << 0) as _),
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<i32>() = (e).take_handle() as i32;
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec3 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                                    let len3 = vec3.len();
+                                    ::core::mem::forget(vec3);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len3;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr3.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_filesystem_open<T: GuestFilesystem>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l4 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                9 => {
+                                    let l6 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l8 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                12 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_filesystem_unlink_cabi<T: GuestFilesystem>(
+                    arg0: *mut u8,
+                    arg1: i32,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let handle0;
+                    let result1 = T::unlink(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
+                        {
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
+                            &handle0
+                        },
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(_) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec3 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                                    let len3 = vec3.len();
+                                    ::core::mem::forget(vec3);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len3;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr3.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_filesystem_unlink<T: GuestFilesystem>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l4 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                9 => {
+                                    let l6 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l8 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                12 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_filesystem_create_dir_cabi<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8, arg1: i32) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let handle0;
+                    let result1 = T::create_dir(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
+                        {
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
+                            &handle0
+                        },
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result1 {
-                        Ok(e) => {
+                        Ok(_) => {
                             *ptr2.add(0).cast::<u8>() = (0i32) as u8;
-                            *ptr2.add(8).cast::<i64>() = _rt::as_i64(e);
                         }
                         Err(e) => {
                             *ptr2.add(0).cast::<u8>() = (1i32) as u8;
-                            let vec3 = (e.into_bytes()).into_boxed_slice();
-                            let ptr3 = vec3.as_ptr().cast::<u8>();
-                            let len3 = vec3.len();
-                            ::core::mem::forget(vec3);
-                            *ptr2.add(12).cast::<usize>() = len3;
-                            *ptr2.add(8).cast::<*mut u8>() = ptr3.cast_mut();
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec3 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                                    let len3 = vec3.len();
+                                    ::core::mem::forget(vec3);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len3;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr3.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
                         }
                     };
                     ptr2
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn __post_return_method_file_seek<T: GuestFile>(
-                    arg0: *mut u8,
-                ) {
+                pub unsafe fn __post_return_method_filesystem_create_dir<
+                    T: GuestFilesystem,
+                >(arg0: *mut u8) {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {}
                         _ => {
-                            let l1 = *arg0.add(8).cast::<*mut u8>();
-                            let l2 = *arg0.add(12).cast::<usize>();
-                            _rt::cabi_dealloc(l1, l2, 1);
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l4 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                9 => {
+                                    let l6 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l8 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                12 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                _ => {}
+                            }
                         }
                     }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_filesystem_read_dir_cabi<
+                pub unsafe fn _export_method_filesystem_create_dir_all_cabi<
                     T: GuestFilesystem,
                 >(arg0: *mut u8, arg1: i32) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
                     let handle0;
-                    let result1 = T::read_dir(
-                        FilesystemBorrow::lift(arg0 as u32 as usize).get(),
+                    let result1 = T::create_dir_all(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
                         {
-                            handle0 = super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
-                                arg1 as u32,
-                            );
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
                             &handle0
                         },
                     );
-                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
                     match result1 {
-                        Ok(e) => {
+                        Ok(_) => {
                             *ptr2.add(0).cast::<u8>() = (0i32) as u8;
-                            let vec3 = e;
-                            let len3 = vec3.len();
-                            let layout3 = _rt::alloc::Layout::from_size_align_unchecked(
-                                vec3.len() * 4,
-                                4,
-                            );
-                            let result3 = if layout3.size() != 0 {
-                                let ptr = _rt::alloc::alloc(layout3).cast::<u8>();
-                                if ptr.is_null() {
-                                    _rt::alloc::handle_alloc_error(layout3);
-                                }
-                                ptr
-                            } else {
-                                ::core::ptr::null_mut()
-                            };
-                            for (i, e) in vec3.into_iter().enumerate() {
-                                let base = result3.add(i * 4);
-                                {
-                                    *base.add(0).cast::<i32>() = (e).take_handle() as i32;
-                                }
-                            }
-                            *ptr2.add(8).cast::<usize>() = len3;
-                            *ptr2.add(4).cast::<*mut u8>() = result3;
                         }
                         Err(e) => {
                             *ptr2.add(0).cast::<u8>() = (1i32) as u8;
-                            let vec4 = (e.into_bytes()).into_boxed_slice();
-                            let ptr4 = vec4.as_ptr().cast::<u8>();
-                            let len4 = vec4.len();
-                            ::core::mem::forget(vec4);
-                            *ptr2.add(8).cast::<usize>() = len4;
-                            *ptr2.add(4).cast::<*mut u8>() = ptr4.cast_mut();
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec3 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                                    let len3 = vec3.len();
+                                    ::core::mem::forget(vec3);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len3;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr3.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr2
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr2
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr2
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
                         }
                     };
                     ptr2
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn __post_return_method_filesystem_read_dir<
+                pub unsafe fn __post_return_method_filesystem_create_dir_all<
                     T: GuestFilesystem,
                 >(arg0: *mut u8) {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
-                        0 => {
-                            let l1 = *arg0.add(4).cast::<*mut u8>();
-                            let l2 = *arg0.add(8).cast::<usize>();
-                            let base3 = l1;
-                            let len3 = l2;
-                            _rt::cabi_dealloc(base3, len3 * 4, 4);
-                        }
+                        0 => {}
                         _ => {
-                            let l4 = *arg0.add(4).cast::<*mut u8>();
-                            let l5 = *arg0.add(8).cast::<usize>();
-                            _rt::cabi_dealloc(l4, l5, 1);
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l4 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                9 => {
+                                    let l6 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l8 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                12 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                _ => {}
+                            }
                         }
                     }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_filesystem_stat_cabi<T: GuestFilesystem>(
+                pub unsafe fn _export_method_filesystem_rename_cabi<T: GuestFilesystem>(
                     arg0: *mut u8,
                     arg1: i32,
+                    arg2: i32,
                 ) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
                     let handle0;
-                    let result1 = T::stat(
-                        FilesystemBorrow::lift(arg0 as u32 as usize).get(),
+                    let handle1;
+                    let result2 = T::rename(
+                        unsafe { FilesystemBorrow::lift(arg0 as u32 as usize) }.get(),
                         {
-                            handle0 = super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
-                                arg1 as u32,
-                            );
+                            handle0 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg1 as u32,
+                                )
+                            };
                             &handle0
                         },
+                        {
+                            handle1 = unsafe {
+                                super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
+                                    arg2 as u32,
+                                )
+                            };
+                            &handle1
+                        },
                     );
-                    let ptr2 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
-                    match result1 {
-                        Ok(e) => {
-                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
-                            let Stat { kind: kind3, size: size3 } = e;
-                            *ptr2.add(8).cast::<u8>() = (kind3.clone() as i32) as u8;
-                            *ptr2.add(16).cast::<i64>() = _rt::as_i64(size3);
+                    let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result2 {
+                        Ok(_) => {
+                            *ptr3.add(0).cast::<u8>() = (0i32) as u8;
                         }
                         Err(e) => {
-                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
-                            let vec4 = (e.into_bytes()).into_boxed_slice();
-                            let ptr4 = vec4.as_ptr().cast::<u8>();
-                            let len4 = vec4.len();
-                            ::core::mem::forget(vec4);
-                            *ptr2.add(12).cast::<usize>() = len4;
-                            *ptr2.add(8).cast::<*mut u8>() = ptr4.cast_mut();
+                            *ptr3.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec4 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec5 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                                    let len5 = vec5.len();
+                                    ::core::mem::forget(vec5);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len5;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr5.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec6 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr6 = vec6.as_ptr().cast::<u8>();
+                                    let len6 = vec6.len();
+                                    ::core::mem::forget(vec6);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len6;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr6.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec7 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr7 = vec7.as_ptr().cast::<u8>();
+                                    let len7 = vec7.len();
+                                    ::core::mem::forget(vec7);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len7;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr7.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec8 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr8 = vec8.as_ptr().cast::<u8>();
+                                    let len8 = vec8.len();
+                                    ::core::mem::forget(vec8);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len8;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr8.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
                         }
                     };
-                    ptr2
+                    ptr3
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn __post_return_method_filesystem_stat<T: GuestFilesystem>(
+                pub unsafe fn __post_return_method_filesystem_rename<T: GuestFilesystem>(
                     arg0: *mut u8,
                 ) {
                     let l0 = i32::from(*arg0.add(0).cast::<u8>());
                     match l0 {
                         0 => {}
                         _ => {
-                            let l1 = *arg0.add(8).cast::<*mut u8>();
-                            let l2 = *arg0.add(12).cast::<usize>();
-                            _rt::cabi_dealloc(l1, l2, 1);
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l4 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                9 => {
+                                    let l6 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l8 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                12 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                _ => {}
+                            }
                         }
                     }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_method_filesystem_open_cabi<T: GuestFilesystem>(
-                    arg0: *mut u8,
-                    arg1: i32,
-                ) -> *mut u8 {
+                pub unsafe fn _export_info_cabi<T: Guest>() -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::open(
-                        FilesystemBorrow::lift(arg0 as u32 as usize).get(),
-                        super::super::super::super::junkyard_vfs::vfs_plugin::vfs_host::AbsolutePath::from_handle(
-                            arg1 as u32,
-                        ),
+                    let result0 = T::info();
+                    let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    let PluginInfo {
+                        name: name2,
+                        version: version2,
+                        author: author2,
+                        schemes: schemes2,
+                        capabilities: capabilities2,
+                        config_schema: config_schema2,
+                    } = result0;
+                    let vec3 = (name2.into_bytes()).into_boxed_slice();
+                    let ptr3 = vec3.as_ptr().cast::<u8>();
+                    let len3 = vec3.len();
+                    ::core::mem::forget(vec3);
+                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
+                    *ptr1.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                    let vec4 = (version2.into_bytes()).into_boxed_slice();
+                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                    let len4 = vec4.len();
+                    ::core::mem::forget(vec4);
+                    *ptr1.add(3 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len4;
+                    *ptr1
+                        .add(2 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = ptr4.cast_mut();
+                    let vec5 = (author2.into_bytes()).into_boxed_slice();
+                    let ptr5 = vec5.as_ptr().cast::<u8>();
+                    let len5 = vec5.len();
+                    ::core::mem::forget(vec5);
+                    *ptr1.add(5 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+                    *ptr1
+                        .add(4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = ptr5.cast_mut();
+                    let vec7 = schemes2;
+                    let len7 = vec7.len();
+                    let layout7 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec7.len() * (2 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
                     );
-                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
-                    match result0 {
-                        Ok(e) => {
-                            *ptr1.add(0).cast::<u8>() = (0i32) as u8;
-                            *ptr1.add(4).cast::<i32>() = (e).take_handle() as i32;
+                    let result7 = if layout7.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout7).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout7);
                         }
-                        Err(e) => {
-                            *ptr1.add(0).cast::<u8>() = (1i32) as u8;
-                            let vec2 = (e.into_bytes()).into_boxed_slice();
-                            let ptr2 = vec2.as_ptr().cast::<u8>();
-                            let len2 = vec2.len();
-                            ::core::mem::forget(vec2);
-                            *ptr1.add(8).cast::<usize>() = len2;
-                            *ptr1.add(4).cast::<*mut u8>() = ptr2.cast_mut();
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec7.into_iter().enumerate() {
+                        let base = result7
+                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let vec6 = (e.into_bytes()).into_boxed_slice();
+                            let ptr6 = vec6.as_ptr().cast::<u8>();
+                            let len6 = vec6.len();
+                            ::core::mem::forget(vec6);
+                            *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len6;
+                            *base.add(0).cast::<*mut u8>() = ptr6.cast_mut();
+                        }
+                    }
+                    *ptr1.add(7 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len7;
+                    *ptr1
+                        .add(6 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = result7;
+                    let vec8 = capabilities2;
+                    let len8 = vec8.len();
+                    let layout8 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec8.len() * 1,
+                        1,
+                    );
+                    let result8 = if layout8.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout8).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout8);
                         }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
                     };
+                    for (i, e) in vec8.into_iter().enumerate() {
+                        let base = result8.add(i * 1);
+                        {
+                            *base.add(0).cast::<u8>() = (e.clone() as i32) as u8;
+                        }
+                    }
+                    *ptr1.add(9 * ::core::mem::size_of::<*const u8>()).cast::<usize>() = len8;
+                    *ptr1
+                        .add(8 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = result8;
+                    let vec9 = (config_schema2.into_bytes()).into_boxed_slice();
+                    let ptr9 = vec9.as_ptr().cast::<u8>();
+                    let len9 = vec9.len();
+                    ::core::mem::forget(vec9);
+                    *ptr1
+                        .add(11 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>() = len9;
+                    *ptr1
+                        .add(10 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>() = ptr9.cast_mut();
                     ptr1
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn __post_return_method_filesystem_open<T: GuestFilesystem>(
-                    arg0: *mut u8,
-                ) {
-                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
-                    match l0 {
-                        0 => {}
-                        _ => {
-                            let l1 = *arg0.add(4).cast::<*mut u8>();
-                            let l2 = *arg0.add(8).cast::<usize>();
-                            _rt::cabi_dealloc(l1, l2, 1);
+                pub unsafe fn __post_return_info<T: Guest>(arg0: *mut u8) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l0, l1, 1);
+                    let l2 = *arg0
+                        .add(2 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l3 = *arg0
+                        .add(3 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l2, l3, 1);
+                    let l4 = *arg0
+                        .add(4 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l5 = *arg0
+                        .add(5 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l4, l5, 1);
+                    let l6 = *arg0
+                        .add(6 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l7 = *arg0
+                        .add(7 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base10 = l6;
+                    let len10 = l7;
+                    for i in 0..len10 {
+                        let base = base10
+                            .add(i * (2 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let l8 = *base.add(0).cast::<*mut u8>();
+                            let l9 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l8, l9, 1);
                         }
                     }
+                    _rt::cabi_dealloc(
+                        base10,
+                        len10 * (2 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let l11 = *arg0
+                        .add(8 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l12 = *arg0
+                        .add(9 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base13 = l11;
+                    let len13 = l12;
+                    _rt::cabi_dealloc(base13, len13 * 1, 1);
+                    let l14 = *arg0
+                        .add(10 * ::core::mem::size_of::<*const u8>())
+                        .cast::<*mut u8>();
+                    let l15 = *arg0
+                        .add(11 * ::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    _rt::cabi_dealloc(l14, l15, 1);
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_init_cabi<T: Guest>() -> *mut u8 {
+                pub unsafe fn _export_init_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                ) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
-                    let result0 = T::init();
-                    let ptr1 = _RET_AREA.0.as_mut_ptr().cast::<u8>();
-                    match result0 {
+                    let len0 = arg1;
+                    let bytes0 = _rt::Vec::from_raw_parts(arg0.cast(), len0, len0);
+                    let base7 = arg2;
+                    let len7 = arg3;
+                    let mut result7 = _rt::Vec::with_capacity(len7);
+                    for i in 0..len7 {
+                        let base = base7
+                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                        let e7 = {
+                            let l1 = *base.add(0).cast::<*mut u8>();
+                            let l2 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len3 = l2;
+                            let bytes3 = _rt::Vec::from_raw_parts(l1.cast(), len3, len3);
+                            let l4 = *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l5 = *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let len6 = l5;
+                            let bytes6 = _rt::Vec::from_raw_parts(l4.cast(), len6, len6);
+                            (_rt::string_lift(bytes3), _rt::string_lift(bytes6))
+                        };
+                        result7.push(e7);
+                    }
+                    _rt::cabi_dealloc(
+                        base7,
+                        len7 * (4 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result8 = T::init(MountConfig {
+                        uri: _rt::string_lift(bytes0),
+                        options: result7,
+                    });
+                    let ptr9 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result8 {
                         Ok(e) => {
-                            *ptr1.add(0).cast::<u8>() = (0i32) as u8;
-                            *ptr1.add(4).cast::<i32>() = (e).take_handle() as i32;
+                            *ptr9.add(0).cast::<u8>() = (0i32) as u8;
+                            *ptr9
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<i32>() = (e).take_handle() as i32;
                         }
-                        Err(_) => {
-                            *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+                        Err(e) => {
+                            *ptr9.add(0).cast::<u8>() = (1i32) as u8;
+                            match e {
+                                VfsError::NotFound => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                                VfsError::PermissionDenied => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                }
+                                VfsError::AlreadyExists => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (2i32) as u8;
+                                }
+                                VfsError::NotADirectory => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (3i32) as u8;
+                                }
+                                VfsError::IsADirectory => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (4i32) as u8;
+                                }
+                                VfsError::NotEmpty => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (5i32) as u8;
+                                }
+                                VfsError::InvalidPath(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (6i32) as u8;
+                                    let vec10 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr10 = vec10.as_ptr().cast::<u8>();
+                                    let len10 = vec10.len();
+                                    ::core::mem::forget(vec10);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len10;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr10.cast_mut();
+                                }
+                                VfsError::Unsupported => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (7i32) as u8;
+                                }
+                                VfsError::Io(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (8i32) as u8;
+                                    let vec11 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr11 = vec11.as_ptr().cast::<u8>();
+                                    let len11 = vec11.len();
+                                    ::core::mem::forget(vec11);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len11;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr11.cast_mut();
+                                }
+                                VfsError::PluginTrap(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (9i32) as u8;
+                                    let vec12 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr12 = vec12.as_ptr().cast::<u8>();
+                                    let len12 = vec12.len();
+                                    ::core::mem::forget(vec12);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len12;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr12.cast_mut();
+                                }
+                                VfsError::Timeout => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (10i32) as u8;
+                                }
+                                VfsError::LimitExceeded(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (11i32) as u8;
+                                    let vec13 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr13 = vec13.as_ptr().cast::<u8>();
+                                    let len13 = vec13.len();
+                                    ::core::mem::forget(vec13);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len13;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr13.cast_mut();
+                                }
+                                VfsError::InvalidResponse(e) => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (12i32) as u8;
+                                    let vec14 = (e.into_bytes()).into_boxed_slice();
+                                    let ptr14 = vec14.as_ptr().cast::<u8>();
+                                    let len14 = vec14.len();
+                                    ::core::mem::forget(vec14);
+                                    *ptr9
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len14;
+                                    *ptr9
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr14.cast_mut();
+                                }
+                                VfsError::Cancelled => {
+                                    *ptr9
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (13i32) as u8;
+                                }
+                            }
                         }
                     };
-                    ptr1
+                    ptr9
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_init<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {}
+                                1 => {}
+                                2 => {}
+                                3 => {}
+                                4 => {}
+                                5 => {}
+                                6 => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l2, l3, 1);
+                                }
+                                7 => {}
+                                8 => {
+                                    let l4 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l5 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l4, l5, 1);
+                                }
+                                9 => {
+                                    let l6 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l7 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l6, l7, 1);
+                                }
+                                10 => {}
+                                11 => {
+                                    let l8 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l9 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l8, l9, 1);
+                                }
+                                12 => {
+                                    let l10 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l11 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    _rt::cabi_dealloc(l10, l11, 1);
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
                 }
                 pub trait Guest {
                     type File: GuestFile;
                     type Filesystem: GuestFilesystem;
-                    fn init() -> Result<Filesystem, ()>;
+                    fn info() -> PluginInfo;
+                    /// mounts a filesystem, a plugin may be mounted many times on different
+                    /// sources
+                    fn init(config: MountConfig) -> Result<Filesystem, VfsError>;
                 }
                 pub trait GuestFile: 'static {
                     #[doc(hidden)]
@@ -905,13 +5587,13 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs"
+                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs@1.0.0"
                             )]
-                            extern "C" {
+                            unsafe extern "C" {
                                 #[link_name = "[resource-new]file"]
                                 fn new(_: *mut u8) -> u32;
                             }
-                            new(val)
+                            unsafe { new(val) }
                         }
                     }
                     #[doc(hidden)]
@@ -927,18 +5609,21 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs"
+                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs@1.0.0"
                             )]
-                            extern "C" {
+                            unsafe extern "C" {
                                 #[link_name = "[resource-rep]file"]
                                 fn rep(_: u32) -> *mut u8;
                             }
                             unsafe { rep(handle) }
                         }
                     }
-                    fn read(&self, data: _rt::Vec<u8>) -> Result<u64, _rt::String>;
-                    fn write(&self, data: _rt::Vec<u8>) -> Result<u64, _rt::String>;
-                    fn seek(&self, s: Seek) -> Result<u64, _rt::String>;
+                    /// reads at most `len` bytes, an empty list means end of file
+                    fn read(&self, len: u64) -> Result<_rt::Vec<u8>, VfsError>;
+                    /// writes a prefix of `data` and returns its length, the caller
+                    /// sends the rest in another call
+                    fn write(&self, data: _rt::Vec<u8>) -> Result<u64, VfsError>;
+                    fn seek(&self, s: Seek) -> Result<u64, VfsError>;
                 }
                 pub trait GuestFilesystem: 'static {
                     #[doc(hidden)]
@@ -954,13 +5639,13 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs"
+                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs@1.0.0"
                             )]
-                            extern "C" {
+                            unsafe extern "C" {
                                 #[link_name = "[resource-new]filesystem"]
                                 fn new(_: *mut u8) -> u32;
                             }
-                            new(val)
+                            unsafe { new(val) }
                         }
                     }
                     #[doc(hidden)]
@@ -976,9 +5661,9 @@ pub mod exports {
                         #[cfg(target_arch = "wasm32")]
                         {
                             #[link(
-                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs"
+                                wasm_import_module = "[export]junkyard-vfs:vfs-plugin/vfs@1.0.0"
                             )]
-                            extern "C" {
+                            unsafe extern "C" {
                                 #[link_name = "[resource-rep]filesystem"]
                                 fn rep(_: u32) -> *mut u8;
                             }
@@ -988,103 +5673,263 @@ pub mod exports {
                     fn read_dir(
                         &self,
                         path: &AbsolutePath,
-                    ) -> Result<_rt::Vec<AbsolutePath>, _rt::String>;
-                    fn stat(&self, path: &AbsolutePath) -> Result<Stat, _rt::String>;
-                    fn open(&self, path: AbsolutePath) -> Result<File, _rt::String>;
+                    ) -> Result<_rt::Vec<AbsolutePath>, VfsError>;
+                    fn read_dir_with_stat(
+                        &self,
+                        path: &AbsolutePath,
+                    ) -> Result<_rt::Vec<DirEntry>, VfsError>;
+                    fn stat(&self, path: &AbsolutePath) -> Result<Stat, VfsError>;
+                    /// like stat, but does not follow symbolic links
+                    fn lstat(&self, path: &AbsolutePath) -> Result<Stat, VfsError>;
+                    fn read_link(
+                        &self,
+                        path: &AbsolutePath,
+                    ) -> Result<_rt::String, VfsError>;
+                    fn create_symlink(
+                        &self,
+                        target: _rt::String,
+                        link: &AbsolutePath,
+                    ) -> Result<(), VfsError>;
+                    fn open(
+                        &self,
+                        path: &AbsolutePath,
+                        options: OpenFlags,
+                    ) -> Result<File, VfsError>;
+                    fn unlink(&self, path: &AbsolutePath) -> Result<(), VfsError>;
+                    fn create_dir(&self, path: &AbsolutePath) -> Result<(), VfsError>;
+                    fn create_dir_all(
+                        &self,
+                        path: &AbsolutePath,
+                    ) -> Result<(), VfsError>;
+                    fn rename(
+                        &self,
+                        from_path: &AbsolutePath,
+                        to_path: &AbsolutePath,
+                    ) -> Result<(), VfsError>;
                 }
                 #[doc(hidden)]
                 #[macro_export]
-                macro_rules! __export_junkyard_vfs_vfs_plugin_vfs_cabi {
+                macro_rules! __export_junkyard_vfs_vfs_plugin_vfs_1_0_0_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
-                        const _ : () = { #[export_name =
-                        "junkyard-vfs:vfs-plugin/vfs#[method]file.read"] unsafe extern
-                        "C" fn export_method_file_read(arg0 : * mut u8, arg1 : * mut u8,
-                        arg2 : usize,) -> * mut u8 { $($path_to_types)*::
+                        const _ : () = { #[unsafe (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]file.read")] unsafe
+                        extern "C" fn export_method_file_read(arg0 : * mut u8, arg1 :
+                        i64,) -> * mut u8 { unsafe { $($path_to_types)*::
                         _export_method_file_read_cabi::<<$ty as $($path_to_types)*::
-                        Guest >::File > (arg0, arg1, arg2) } #[export_name =
-                        "cabi_post_junkyard-vfs:vfs-plugin/vfs#[method]file.read"] unsafe
-                        extern "C" fn _post_return_method_file_read(arg0 : * mut u8,) {
-                        $($path_to_types)*:: __post_return_method_file_read::<<$ty as
-                        $($path_to_types)*:: Guest >::File > (arg0) } #[export_name =
-                        "junkyard-vfs:vfs-plugin/vfs#[method]file.write"] unsafe extern
-                        "C" fn export_method_file_write(arg0 : * mut u8, arg1 : * mut u8,
-                        arg2 : usize,) -> * mut u8 { $($path_to_types)*::
-                        _export_method_file_write_cabi::<<$ty as $($path_to_types)*::
-                        Guest >::File > (arg0, arg1, arg2) } #[export_name =
-                        "cabi_post_junkyard-vfs:vfs-plugin/vfs#[method]file.write"]
+                        Guest >::File > (arg0, arg1) } } #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]file.read")]
+                        unsafe extern "C" fn _post_return_method_file_read(arg0 : * mut
+                        u8,) { unsafe { $($path_to_types)*::
+                        __post_return_method_file_read::<<$ty as $($path_to_types)*::
+                        Guest >::File > (arg0) } } #[unsafe (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]file.write")] unsafe
+                        extern "C" fn export_method_file_write(arg0 : * mut u8, arg1 : *
+                        mut u8, arg2 : usize,) -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_method_file_write_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::File > (arg0, arg1, arg2) } }
+                        #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]file.write")]
                         unsafe extern "C" fn _post_return_method_file_write(arg0 : * mut
-                        u8,) { $($path_to_types)*::
+                        u8,) { unsafe { $($path_to_types)*::
                         __post_return_method_file_write::<<$ty as $($path_to_types)*::
-                        Guest >::File > (arg0) } #[export_name =
-                        "junkyard-vfs:vfs-plugin/vfs#[method]file.seek"] unsafe extern
-                        "C" fn export_method_file_seek(arg0 : * mut u8, arg1 : i32, arg2
-                        : i64,) -> * mut u8 { $($path_to_types)*::
+                        Guest >::File > (arg0) } } #[unsafe (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]file.seek")] unsafe
+                        extern "C" fn export_method_file_seek(arg0 : * mut u8, arg1 :
+                        i32, arg2 : i64,) -> * mut u8 { unsafe { $($path_to_types)*::
                         _export_method_file_seek_cabi::<<$ty as $($path_to_types)*::
-                        Guest >::File > (arg0, arg1, arg2) } #[export_name =
-                        "cabi_post_junkyard-vfs:vfs-plugin/vfs#[method]file.seek"] unsafe
-                        extern "C" fn _post_return_method_file_seek(arg0 : * mut u8,) {
-                        $($path_to_types)*:: __post_return_method_file_seek::<<$ty as
-                        $($path_to_types)*:: Guest >::File > (arg0) } #[export_name =
-                        "junkyard-vfs:vfs-plugin/vfs#[method]filesystem.read-dir"] unsafe
-                        extern "C" fn export_method_filesystem_read_dir(arg0 : * mut u8,
-                        arg1 : i32,) -> * mut u8 { $($path_to_types)*::
+                        Guest >::File > (arg0, arg1, arg2) } } #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]file.seek")]
+                        unsafe extern "C" fn _post_return_method_file_seek(arg0 : * mut
+                        u8,) { unsafe { $($path_to_types)*::
+                        __post_return_method_file_seek::<<$ty as $($path_to_types)*::
+                        Guest >::File > (arg0) } } #[unsafe (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.read-dir")]
+                        unsafe extern "C" fn export_method_filesystem_read_dir(arg0 : *
+                        mut u8, arg1 : i32,) -> * mut u8 { unsafe { $($path_to_types)*::
                         _export_method_filesystem_read_dir_cabi::<<$ty as
-                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) }
-                        #[export_name =
-                        "cabi_post_junkyard-vfs:vfs-plugin/vfs#[method]filesystem.read-dir"]
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) } }
+                        #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.read-dir")]
                         unsafe extern "C" fn _post_return_method_filesystem_read_dir(arg0
-                        : * mut u8,) { $($path_to_types)*::
+                        : * mut u8,) { unsafe { $($path_to_types)*::
                         __post_return_method_filesystem_read_dir::<<$ty as
-                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } #[export_name
-                        = "junkyard-vfs:vfs-plugin/vfs#[method]filesystem.stat"] unsafe
-                        extern "C" fn export_method_filesystem_stat(arg0 : * mut u8, arg1
-                        : i32,) -> * mut u8 { $($path_to_types)*::
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.read-dir-with-stat")]
+                        unsafe extern "C" fn
+                        export_method_filesystem_read_dir_with_stat(arg0 : * mut u8, arg1
+                        : i32,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_method_filesystem_read_dir_with_stat_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) } }
+                        #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.read-dir-with-stat")]
+                        unsafe extern "C" fn
+                        _post_return_method_filesystem_read_dir_with_stat(arg0 : * mut
+                        u8,) { unsafe { $($path_to_types)*::
+                        __post_return_method_filesystem_read_dir_with_stat::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.stat")]
+                        unsafe extern "C" fn export_method_filesystem_stat(arg0 : * mut
+                        u8, arg1 : i32,) -> * mut u8 { unsafe { $($path_to_types)*::
                         _export_method_filesystem_stat_cabi::<<$ty as
-                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) }
-                        #[export_name =
-                        "cabi_post_junkyard-vfs:vfs-plugin/vfs#[method]filesystem.stat"]
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) } }
+                        #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.stat")]
                         unsafe extern "C" fn _post_return_method_filesystem_stat(arg0 : *
-                        mut u8,) { $($path_to_types)*::
+                        mut u8,) { unsafe { $($path_to_types)*::
                         __post_return_method_filesystem_stat::<<$ty as
-                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } #[export_name
-                        = "junkyard-vfs:vfs-plugin/vfs#[method]filesystem.open"] unsafe
-                        extern "C" fn export_method_filesystem_open(arg0 : * mut u8, arg1
-                        : i32,) -> * mut u8 { $($path_to_types)*::
-                        _export_method_filesystem_open_cabi::<<$ty as
-                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) }
-                        #[export_name =
-                        "cabi_post_junkyard-vfs:vfs-plugin/vfs#[method]filesystem.open"]
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.lstat")]
+                        unsafe extern "C" fn export_method_filesystem_lstat(arg0 : * mut
+                        u8, arg1 : i32,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_method_filesystem_lstat_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) } }
+                        #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.lstat")]
+                        unsafe extern "C" fn _post_return_method_filesystem_lstat(arg0 :
+                        * mut u8,) { unsafe { $($path_to_types)*::
+                        __post_return_method_filesystem_lstat::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.read-link")]
+                        unsafe extern "C" fn export_method_filesystem_read_link(arg0 : *
+                        mut u8, arg1 : i32,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_method_filesystem_read_link_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) } }
+                        #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.read-link")]
+                        unsafe extern "C" fn
+                        _post_return_method_filesystem_read_link(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*::
+                        __post_return_method_filesystem_read_link::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.create-symlink")]
+                        unsafe extern "C" fn export_method_filesystem_create_symlink(arg0
+                        : * mut u8, arg1 : * mut u8, arg2 : usize, arg3 : i32,) -> * mut
+                        u8 { unsafe { $($path_to_types)*::
+                        _export_method_filesystem_create_symlink_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1, arg2,
+                        arg3) } } #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.create-symlink")]
+                        unsafe extern "C" fn
+                        _post_return_method_filesystem_create_symlink(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*::
+                        __post_return_method_filesystem_create_symlink::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.open")]
+                        unsafe extern "C" fn export_method_filesystem_open(arg0 : * mut
+                        u8, arg1 : i32, arg2 : i32,) -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_method_filesystem_open_cabi::<<$ty
+                        as $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1, arg2)
+                        } } #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.open")]
                         unsafe extern "C" fn _post_return_method_filesystem_open(arg0 : *
-                        mut u8,) { $($path_to_types)*::
+                        mut u8,) { unsafe { $($path_to_types)*::
                         __post_return_method_filesystem_open::<<$ty as
-                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } #[export_name
-                        = "junkyard-vfs:vfs-plugin/vfs#init"] unsafe extern "C" fn
-                        export_init() -> * mut u8 { $($path_to_types)*::
-                        _export_init_cabi::<$ty > () } const _ : () = { #[doc(hidden)]
-                        #[export_name = "junkyard-vfs:vfs-plugin/vfs#[dtor]file"]
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.unlink")]
+                        unsafe extern "C" fn export_method_filesystem_unlink(arg0 : * mut
+                        u8, arg1 : i32,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_method_filesystem_unlink_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) } }
+                        #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.unlink")]
+                        unsafe extern "C" fn _post_return_method_filesystem_unlink(arg0 :
+                        * mut u8,) { unsafe { $($path_to_types)*::
+                        __post_return_method_filesystem_unlink::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.create-dir")]
+                        unsafe extern "C" fn export_method_filesystem_create_dir(arg0 : *
+                        mut u8, arg1 : i32,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_method_filesystem_create_dir_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) } }
+                        #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.create-dir")]
+                        unsafe extern "C" fn
+                        _post_return_method_filesystem_create_dir(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*::
+                        __post_return_method_filesystem_create_dir::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.create-dir-all")]
+                        unsafe extern "C" fn export_method_filesystem_create_dir_all(arg0
+                        : * mut u8, arg1 : i32,) -> * mut u8 { unsafe {
+                        $($path_to_types)*::
+                        _export_method_filesystem_create_dir_all_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1) } }
+                        #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.create-dir-all")]
+                        unsafe extern "C" fn
+                        _post_return_method_filesystem_create_dir_all(arg0 : * mut u8,) {
+                        unsafe { $($path_to_types)*::
+                        __post_return_method_filesystem_create_dir_all::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.rename")]
+                        unsafe extern "C" fn export_method_filesystem_rename(arg0 : * mut
+                        u8, arg1 : i32, arg2 : i32,) -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_method_filesystem_rename_cabi::<<$ty
+                        as $($path_to_types)*:: Guest >::Filesystem > (arg0, arg1, arg2)
+                        } } #[unsafe (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#[method]filesystem.rename")]
+                        unsafe extern "C" fn _post_return_method_filesystem_rename(arg0 :
+                        * mut u8,) { unsafe { $($path_to_types)*::
+                        __post_return_method_filesystem_rename::<<$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (arg0) } } #[unsafe
+                        (export_name = "junkyard-vfs:vfs-plugin/vfs@1.0.0#info")] unsafe
+                        extern "C" fn export_info() -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_info_cabi::<$ty > () } } #[unsafe
+                        (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#info")] unsafe
+                        extern "C" fn _post_return_info(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_info::<$ty > (arg0) } }
+                        #[unsafe (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#init")] unsafe extern "C" fn
+                        export_init(arg0 : * mut u8, arg1 : usize, arg2 : * mut u8, arg3
+                        : usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_init_cabi::<$ty > (arg0, arg1, arg2, arg3) } } #[unsafe
+                        (export_name =
+                        "cabi_post_junkyard-vfs:vfs-plugin/vfs@1.0.0#init")] unsafe
+                        extern "C" fn _post_return_init(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_init::<$ty > (arg0) } } const
+                        _ : () = { #[doc(hidden)] #[unsafe (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[dtor]file")]
                         #[allow(non_snake_case)] unsafe extern "C" fn dtor(rep : * mut
-                        u8) { $($path_to_types)*:: File::dtor::< <$ty as
-                        $($path_to_types)*:: Guest >::File > (rep) } }; const _ : () = {
-                        #[doc(hidden)] #[export_name =
-                        "junkyard-vfs:vfs-plugin/vfs#[dtor]filesystem"]
+                        u8) { unsafe { $($path_to_types)*:: File::dtor::< <$ty as
+                        $($path_to_types)*:: Guest >::File > (rep) } } }; const _ : () =
+                        { #[doc(hidden)] #[unsafe (export_name =
+                        "junkyard-vfs:vfs-plugin/vfs@1.0.0#[dtor]filesystem")]
                         #[allow(non_snake_case)] unsafe extern "C" fn dtor(rep : * mut
-                        u8) { $($path_to_types)*:: Filesystem::dtor::< <$ty as
-                        $($path_to_types)*:: Guest >::Filesystem > (rep) } }; };
+                        u8) { unsafe { $($path_to_types)*:: Filesystem::dtor::< <$ty as
+                        $($path_to_types)*:: Guest >::Filesystem > (rep) } } }; };
                     };
                 }
                 #[doc(hidden)]
-                pub use __export_junkyard_vfs_vfs_plugin_vfs_cabi;
+                pub use __export_junkyard_vfs_vfs_plugin_vfs_1_0_0_cabi;
                 #[repr(align(8))]
-                struct _RetArea([::core::mem::MaybeUninit<u8>; 24]);
+                struct _RetArea(
+                    [::core::mem::MaybeUninit<
+                        u8,
+                    >; 168 + 6 * ::core::mem::size_of::<*const u8>()],
+                );
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 24],
+                    [::core::mem::MaybeUninit::uninit(); 168
+                        + 6 * ::core::mem::size_of::<*const u8>()],
                 );
             }
         }
     }
 }
 #[rustfmt::skip]
+#[rustfmt::skip]
 mod _rt {
+    #![allow(dead_code, clippy::all)]
     use core::fmt;
     use core::marker;
     use core::sync::atomic::{AtomicU32, Ordering::Relaxed};
@@ -1159,8 +6004,8 @@ mod _rt {
             }
         }
     }
-    pub use alloc_crate::vec::Vec;
     pub use alloc_crate::string::String;
+    pub use alloc_crate::vec::Vec;
     pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
         if cfg!(debug_assertions) {
             String::from_utf8(bytes).unwrap()
@@ -1186,10 +6031,12 @@ mod _rt {
             val != 0
         }
     }
-    pub use alloc_crate::boxed::Box;
-    #[cfg(target_arch = "wasm32")]
-    pub fn run_ctors_once() {
-        wit_bindgen_rt::run_ctors_once();
+    pub unsafe fn invalid_enum_discriminant<T>() -> T {
+        if cfg!(debug_assertions) {
+            panic!("invalid enum discriminant")
+        } else {
+            unsafe { core::hint::unreachable_unchecked() }
+        }
     }
     pub fn as_i64<T: AsI64>(t: T) -> i64 {
         t.as_i64()
@@ -1214,11 +6061,75 @@ mod _rt {
             self as i64
         }
     }
+    pub use alloc_crate::boxed::Box;
+    #[cfg(target_arch = "wasm32")]
+    pub fn run_ctors_once() {
+        wit_bindgen_rt::run_ctors_once();
+    }
     pub use alloc_crate::alloc;
+    pub fn as_i32<T: AsI32>(t: T) -> i32 {
+        t.as_i32()
+    }
+    pub trait AsI32 {
+        fn as_i32(self) -> i32;
+    }
+    impl<'a, T: Copy + AsI32> AsI32 for &'a T {
+        fn as_i32(self) -> i32 {
+            (*self).as_i32()
+        }
+    }
+    impl AsI32 for i32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u32 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u16 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for i8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for u8 {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for char {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
+    impl AsI32 for usize {
+        #[inline]
+        fn as_i32(self) -> i32 {
+            self as i32
+        }
+    }
     extern crate alloc as alloc_crate;
 }
-/// Generates `#[no_mangle]` functions to export the specified type as the
-/// root implementation of all generated traits.
+/// Generates `#[unsafe(no_mangle)]` functions to export the specified type as
+/// the root implementation of all generated traits.
 ///
 /// For more information see the documentation of `wit_bindgen::generate!`.
 ///
@@ -1242,38 +6153,95 @@ macro_rules! __export_vfs_plugin_impl {
     };
     ($ty:ident with_types_in $($path_to_types_root:tt)*) => {
         $($path_to_types_root)*::
-        exports::junkyard_vfs::vfs_plugin::vfs::__export_junkyard_vfs_vfs_plugin_vfs_cabi!($ty
+        exports::junkyard_vfs::vfs_plugin::vfs::__export_junkyard_vfs_vfs_plugin_vfs_1_0_0_cabi!($ty
         with_types_in $($path_to_types_root)*:: exports::junkyard_vfs::vfs_plugin::vfs);
+        const _ : () = { #[cfg(target_arch = "wasm32")] #[unsafe (link_section =
+        "component-type:wit-bindgen:0.41.0:junkyard-vfs:vfs-plugin@1.0.0:vfs-plugin:imports and exports")]
+        #[doc(hidden)] #[allow(clippy::octal_escapes)] pub static
+        __WIT_BINDGEN_COMPONENT_TYPE : [u8; 2438] = *
+        b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\x85\x12\x01A\x02\x01\
+A\x05\x01B'\x04\0\x0dabsolute-path\x03\x01\x01m\x05\x05trace\x05debug\x04info\x04\
+warn\x05error\x04\0\x09log-level\x03\0\x01\x01q\x02\x0equota-exceeded\0\0\x02io\x01\
+s\0\x04\0\x0dstorage-error\x03\0\x03\x01h\0\x01ps\x01@\x01\x04self\x05\0\x06\x04\
+\0\x20[method]absolute-path.components\x01\x07\x01@\x01\x04self\x05\0\x7f\x04\0\x1d\
+[method]absolute-path.is-root\x01\x08\x01i\0\x01@\x01\x04self\x05\0\x09\x04\0\x1c\
+[method]absolute-path.parent\x01\x0a\x01@\x01\x04self\x05\0s\x04\0\x1f[method]ab\
+solute-path.file-name\x01\x0b\x04\0\x1a[method]absolute-path.path\x01\x0b\x01@\x01\
+\x01ss\0\x09\x04\0\x14create-absolute-path\x01\x0c\x01@\x02\x05level\x02\x07mess\
+ages\x01\0\x04\0\x03log\x01\x0d\x01p}\x01k\x0e\x01@\x01\x03keys\0\x0f\x04\0\x0bs\
+torage-get\x01\x10\x01j\0\x01\x04\x01@\x02\x03keys\x05value\x0e\0\x11\x04\0\x0bs\
+torage-set\x01\x12\x01@\x01\x03keys\0\x11\x04\0\x0estorage-delete\x01\x13\x01@\0\
+\0\x06\x04\0\x0cstorage-list\x01\x14\x01ks\x01@\x02\x05titles\x0bdescriptions\0\x15\
+\x04\0\x0dprompt-secret\x01\x16\x01@\x03\x04donew\x05totalw\x07messages\x01\0\x04\
+\0\x0freport-progress\x01\x17\x01@\0\0\x7f\x04\0\x0cis-cancelled\x01\x18\x03\0&j\
+unkyard-vfs:vfs-plugin/vfs-host@1.0.0\x05\0\x02\x03\0\0\x0dabsolute-path\x01BO\x02\
+\x03\x02\x01\x01\x04\0\x0dabsolute-path\x03\0\0\x01q\x03\x05start\x01w\0\x07curr\
+ent\x01x\0\x03end\x01x\0\x04\0\x04seek\x03\0\x02\x01m\x04\x04file\x06folder\x04l\
+ink\x07unknown\x04\0\x04kind\x03\0\x04\x01r\x02\x07secondsx\x0bnanosecondsy\x04\0\
+\x08datetime\x03\0\x06\x01k\x07\x01ky\x01ks\x01kw\x01r\x0d\x04kind\x05\x04sizew\x08\
+modified\x08\x08accessed\x08\x07created\x08\x04mode\x09\x03uid\x09\x03gid\x09\x04\
+user\x0a\x05group\x0a\x05inode\x0b\x06device\x0b\x05nlink\x0b\x04\0\x04stat\x03\0\
+\x0c\x01q\x0e\x09not-found\0\0\x11permission-denied\0\0\x0ealready-exists\0\0\x0f\
+not-a-directory\0\0\x0eis-a-directory\0\0\x09not-empty\0\0\x0cinvalid-path\x01s\0\
+\x0bunsupported\0\0\x02io\x01s\0\x0bplugin-trap\x01s\0\x07timeout\0\0\x0elimit-e\
+xceeded\x01s\0\x10invalid-response\x01s\0\x09cancelled\0\0\x04\0\x09vfs-error\x03\
+\0\x0e\x01r\x02\x04names\x04stat\x0d\x04\0\x09dir-entry\x03\0\x10\x01n\x05\x06cr\
+eate\x04read\x05write\x08truncate\x06append\x04\0\x0aopen-flags\x03\0\x12\x01m\x04\
+\x04read\x05write\x05links\x06rename\x04\0\x0acapability\x03\0\x14\x01ps\x01p\x15\
+\x01r\x06\x04names\x07versions\x06authors\x07schemes\x16\x0ccapabilities\x17\x0d\
+config-schemas\x04\0\x0bplugin-info\x03\0\x18\x01o\x02ss\x01p\x1a\x01r\x02\x03ur\
+is\x07options\x1b\x04\0\x0cmount-config\x03\0\x1c\x04\0\x04file\x03\x01\x04\0\x0a\
+filesystem\x03\x01\x01h\x1e\x01p}\x01j\x01!\x01\x0f\x01@\x02\x04self\x20\x03lenw\
+\0\"\x04\0\x11[method]file.read\x01#\x01j\x01w\x01\x0f\x01@\x02\x04self\x20\x04d\
+ata!\0$\x04\0\x12[method]file.write\x01%\x01@\x02\x04self\x20\x01s\x03\0$\x04\0\x11\
+[method]file.seek\x01&\x01h\x1f\x01h\x01\x01i\x01\x01p)\x01j\x01*\x01\x0f\x01@\x02\
+\x04self'\x04path(\0+\x04\0\x1b[method]filesystem.read-dir\x01,\x01p\x11\x01j\x01\
+-\x01\x0f\x01@\x02\x04self'\x04path(\0.\x04\0%[method]filesystem.read-dir-with-s\
+tat\x01/\x01j\x01\x0d\x01\x0f\x01@\x02\x04self'\x04path(\00\x04\0\x17[method]fil\
+esystem.stat\x011\x04\0\x18[method]filesystem.lstat\x011\x01j\x01s\x01\x0f\x01@\x02\
+\x04self'\x04path(\02\x04\0\x1c[method]filesystem.read-link\x013\x01j\0\x01\x0f\x01\
+@\x03\x04self'\x06targets\x04link(\04\x04\0![method]filesystem.create-symlink\x01\
+5\x01i\x1e\x01j\x016\x01\x0f\x01@\x03\x04self'\x04path(\x07options\x13\07\x04\0\x17\
+[method]filesystem.open\x018\x01@\x02\x04self'\x04path(\04\x04\0\x19[method]file\
+system.unlink\x019\x04\0\x1d[method]filesystem.create-dir\x019\x04\0![method]fil\
+esystem.create-dir-all\x019\x01@\x03\x04self'\x09from-path(\x07to-path(\04\x04\0\
+\x19[method]filesystem.rename\x01:\x01@\0\0\x19\x04\0\x04info\x01;\x01i\x1f\x01j\
+\x01<\x01\x0f\x01@\x01\x06config\x1d\0=\x04\0\x04init\x01>\x04\0!junkyard-vfs:vf\
+s-plugin/vfs@1.0.0\x05\x02\x04\0(junkyard-vfs:vfs-plugin/vfs-plugin@1.0.0\x04\0\x0b\
+\x10\x01\0\x0avfs-plugin\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-\
+component\x070.227.1\x10wit-bindgen-rust\x060.41.0";
+        };
     };
 }
 #[doc(inline)]
 pub use __export_vfs_plugin_impl as export;
 #[cfg(target_arch = "wasm32")]
-#[link_section = "component-type:wit-bindgen:0.36.0:junkyard-vfs:vfs-plugin:vfs-plugin:encoded world"]
+#[unsafe(
+    link_section = "component-type:wit-bindgen:0.41.0:junkyard-vfs:vfs-plugin@1.0.0:vfs-plugin-with-all-of-its-exports-removed:encoded world"
+)]
 #[doc(hidden)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 997] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xe4\x06\x01A\x02\x01\
-A\x05\x01B\x0f\x04\0\x0dabsolute-path\x03\x01\x01h\0\x01ps\x01@\x01\x04self\x01\0\
-\x02\x04\0\x20[method]absolute-path.components\x01\x03\x01@\x01\x04self\x01\0\x7f\
-\x04\0\x1d[method]absolute-path.is-root\x01\x04\x01i\0\x01@\x01\x04self\x01\0\x05\
-\x04\0\x1c[method]absolute-path.parent\x01\x06\x01@\x01\x04self\x01\0s\x04\0\x1f\
-[method]absolute-path.file-name\x01\x07\x04\0\x1a[method]absolute-path.path\x01\x07\
-\x01@\x01\x01ss\0\x05\x04\0\x14create-absolute-path\x01\x08\x03\0\x20junkyard-vf\
-s:vfs-plugin/vfs-host\x05\0\x02\x03\0\0\x0dabsolute-path\x01B$\x02\x03\x02\x01\x01\
-\x04\0\x0dabsolute-path\x03\0\0\x01q\x03\x05start\x01w\0\x07current\x01x\0\x03en\
-d\x01x\0\x04\0\x04seek\x03\0\x02\x01m\x04\x04file\x06folder\x04link\x07unknown\x04\
-\0\x04kind\x03\0\x04\x01r\x02\x04kind\x05\x04sizew\x04\0\x04stat\x03\0\x06\x04\0\
-\x04file\x03\x01\x04\0\x0afilesystem\x03\x01\x01h\x08\x01p}\x01j\x01w\x01s\x01@\x02\
-\x04self\x0a\x04data\x0b\0\x0c\x04\0\x11[method]file.read\x01\x0d\x04\0\x12[meth\
-od]file.write\x01\x0d\x01@\x02\x04self\x0a\x01s\x03\0\x0c\x04\0\x11[method]file.\
-seek\x01\x0e\x01h\x09\x01h\x01\x01i\x01\x01p\x11\x01j\x01\x12\x01s\x01@\x02\x04s\
-elf\x0f\x04path\x10\0\x13\x04\0\x1b[method]filesystem.read-dir\x01\x14\x01j\x01\x07\
-\x01s\x01@\x02\x04self\x0f\x04path\x10\0\x15\x04\0\x17[method]filesystem.stat\x01\
-\x16\x01i\x08\x01j\x01\x17\x01s\x01@\x02\x04self\x0f\x04path\x11\0\x18\x04\0\x17\
-[method]filesystem.open\x01\x19\x01i\x09\x01j\x01\x1a\0\x01@\0\0\x1b\x04\0\x04in\
-it\x01\x1c\x04\0\x1bjunkyard-vfs:vfs-plugin/vfs\x05\x02\x04\0\"junkyard-vfs:vfs-\
-plugin/vfs-plugin\x04\0\x0b\x10\x01\0\x0avfs-plugin\x03\0\0\0G\x09producers\x01\x0c\
-processed-by\x02\x0dwit-component\x070.220.1\x10wit-bindgen-rust\x060.36.0";
+#[allow(clippy::octal_escapes)]
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 921] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xf8\x05\x01A\x02\x01\
+A\x02\x01B'\x04\0\x0dabsolute-path\x03\x01\x01m\x05\x05trace\x05debug\x04info\x04\
+warn\x05error\x04\0\x09log-level\x03\0\x01\x01q\x02\x0equota-exceeded\0\0\x02io\x01\
+s\0\x04\0\x0dstorage-error\x03\0\x03\x01h\0\x01ps\x01@\x01\x04self\x05\0\x06\x04\
+\0\x20[method]absolute-path.components\x01\x07\x01@\x01\x04self\x05\0\x7f\x04\0\x1d\
+[method]absolute-path.is-root\x01\x08\x01i\0\x01@\x01\x04self\x05\0\x09\x04\0\x1c\
+[method]absolute-path.parent\x01\x0a\x01@\x01\x04self\x05\0s\x04\0\x1f[method]ab\
+solute-path.file-name\x01\x0b\x04\0\x1a[method]absolute-path.path\x01\x0b\x01@\x01\
+\x01ss\0\x09\x04\0\x14create-absolute-path\x01\x0c\x01@\x02\x05level\x02\x07mess\
+ages\x01\0\x04\0\x03log\x01\x0d\x01p}\x01k\x0e\x01@\x01\x03keys\0\x0f\x04\0\x0bs\
+torage-get\x01\x10\x01j\0\x01\x04\x01@\x02\x03keys\x05value\x0e\0\x11\x04\0\x0bs\
+torage-set\x01\x12\x01@\x01\x03keys\0\x11\x04\0\x0estorage-delete\x01\x13\x01@\0\
+\0\x06\x04\0\x0cstorage-list\x01\x14\x01ks\x01@\x02\x05titles\x0bdescriptions\0\x15\
+\x04\0\x0dprompt-secret\x01\x16\x01@\x03\x04donew\x05totalw\x07messages\x01\0\x04\
+\0\x0freport-progress\x01\x17\x01@\0\0\x7f\x04\0\x0cis-cancelled\x01\x18\x03\0&j\
+unkyard-vfs:vfs-plugin/vfs-host@1.0.0\x05\0\x04\0Hjunkyard-vfs:vfs-plugin/vfs-pl\
+ugin-with-all-of-its-exports-removed@1.0.0\x04\0\x0b0\x01\0*vfs-plugin-with-all-\
+of-its-exports-removed\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-co\
+mponent\x070.227.1\x10wit-bindgen-rust\x060.41.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
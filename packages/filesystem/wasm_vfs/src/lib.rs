@@ -1,16 +1,117 @@
 pub use bindings::exports::junkyard_vfs::vfs_plugin::vfs::{
-    AbsolutePath, File as FileResource, GuestFile as File, GuestFilesystem as Filesystem, Kind,
-    Seek, Stat,
+    AbsolutePath, Capability, Datetime, DirEntry, File as FileResource, GuestFile as File,
+    GuestFilesystem as Filesystem, Kind, MountConfig, OpenFlags, PluginInfo, Seek, Stat, VfsError,
 };
 
-pub use bindings::junkyard_vfs::vfs_plugin::vfs_host::create_absolute_path;
+pub use bindings::junkyard_vfs::vfs_plugin::vfs_host::{
+    create_absolute_path, is_cancelled, log, prompt_secret, report_progress, LogLevel,
+};
 
 #[allow(warnings)]
 pub mod bindings;
 
+/// Values the plugin keeps between sessions, stored by the host for this
+/// plugin only.
+pub mod storage {
+    pub use crate::bindings::junkyard_vfs::vfs_plugin::vfs_host::{
+        storage_delete as delete, storage_get as get, storage_list as list, storage_set as set,
+        StorageError,
+    };
+}
+
+impl From<std::io::Error> for VfsError {
+    fn from(error: std::io::Error) -> Self {
+        use std::io::ErrorKind;
+
+        match error.kind() {
+            ErrorKind::NotFound => VfsError::NotFound,
+            ErrorKind::PermissionDenied => VfsError::PermissionDenied,
+            ErrorKind::AlreadyExists => VfsError::AlreadyExists,
+            ErrorKind::NotADirectory => VfsError::NotADirectory,
+            ErrorKind::IsADirectory => VfsError::IsADirectory,
+            ErrorKind::DirectoryNotEmpty => VfsError::NotEmpty,
+            ErrorKind::Unsupported => VfsError::Unsupported,
+            ErrorKind::InvalidInput => VfsError::InvalidPath(error.to_string()),
+            _ => VfsError::Io(error.to_string()),
+        }
+    }
+}
+
+impl Stat {
+    /// Builds a [`Stat`] that only carries the kind and the size, all the
+    /// other metadata is left empty.
+    pub fn new(kind: Kind, size: u64) -> Stat {
+        Stat {
+            kind,
+            size,
+            modified: None,
+            accessed: None,
+            created: None,
+            mode: None,
+            uid: None,
+            gid: None,
+            user: None,
+            group: None,
+            inode: None,
+            device: None,
+            nlink: None,
+        }
+    }
+}
+
+impl From<std::time::SystemTime> for Datetime {
+    fn from(value: std::time::SystemTime) -> Self {
+        match value.duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => Datetime {
+                seconds: duration.as_secs() as i64,
+                nanoseconds: duration.subsec_nanos(),
+            },
+            Err(before_epoch) => {
+                let duration = before_epoch.duration();
+                if duration.subsec_nanos() == 0 {
+                    Datetime {
+                        seconds: -(duration.as_secs() as i64),
+                        nanoseconds: 0,
+                    }
+                } else {
+                    Datetime {
+                        seconds: -(duration.as_secs() as i64) - 1,
+                        nanoseconds: 1_000_000_000 - duration.subsec_nanos(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl MountConfig {
+    /// The value of the option `key`, the last one if it was set more than
+    /// once.
+    pub fn option(&self, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Exports a plugin, `$f` mounts its filesystem for a [`MountConfig`] and
+/// `$info` describes it.
+/// Without `$info`, the plugin is described by its Cargo package.
 #[macro_export]
 macro_rules! export_vfs {
     ($FS: ty, $F: ty, $f: expr) => {
+        $crate::export_vfs!($FS, $F, $f, $crate::PluginInfo {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            author: env!("CARGO_PKG_AUTHORS").to_string(),
+            schemes: vec![],
+            capabilities: vec![],
+            config_schema: String::new(),
+        });
+    };
+    ($FS: ty, $F: ty, $f: expr, $info: expr) => {
         use std::marker::PhantomData;
         use bindings::exports::junkyard_vfs::vfs_plugin::vfs::{self, Guest};
         use $crate::bindings;
@@ -22,13 +123,20 @@ macro_rules! export_vfs {
 
             type Filesystem = FS;
 
-            fn init() -> Result<vfs::Filesystem, ()> {
-                Ok(vfs::Filesystem::new($f))
+            fn info() -> vfs::PluginInfo {
+                $info
+            }
+
+            fn init(config: vfs::MountConfig) -> Result<vfs::Filesystem, vfs::VfsError> {
+                Ok(vfs::Filesystem::new(($f)(config)?))
             }
         }
 
         type WasmVfsComponent = WasmFilesystem<$FS, $F>;
 
+        // the component exports are not valid symbol names for native
+        // targets, which prevents plugins from being built and tested there
+        #[cfg(target_arch = "wasm32")]
         bindings::export!(WasmVfsComponent with_types_in bindings);
     }
 }
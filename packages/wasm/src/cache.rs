@@ -0,0 +1,307 @@
+use std::{
+    env,
+    fs::{self, File},
+    hash::{Hash, Hasher},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    time::SystemTime,
+};
+
+use anyhow::Result;
+use wasmtime::{component::Component, Engine};
+
+/// How much disk space compiled plugins may use unless configured otherwise.
+pub const DEFAULT_MAX_CACHE_BYTES: u64 = 512 * 1024 * 1024;
+
+const EXTENSION: &str = "cwasm";
+/// Entries start with the hex sha256 of the serialized component.
+const DIGEST_LEN: usize = 64;
+
+/// Compiled plugins, kept so that plugins are not compiled again every time
+/// junkyard starts.
+///
+/// Entries are named after the plugin's content and the configuration of the
+/// engine that compiled them, so a different wasmtime or `Config` never
+/// loads them. The checksum in front of every entry catches files that were
+/// truncated or corrupted on disk; it does not protect against someone who
+/// can write to the cache folder, who could replace the plugins as well.
+pub struct ComponentCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+/// Keeps what is hashed instead of hashing it.
+#[derive(Default)]
+struct HashedBytes(Vec<u8>);
+
+impl Hasher for HashedBytes {
+    fn finish(&self) -> u64 {
+        unreachable!("the bytes are digested instead")
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+}
+
+impl ComponentCache {
+    /// A cache stored in `dir`, a folder that is used for nothing else.
+    pub fn new(dir: impl Into<PathBuf>, max_bytes: u64) -> ComponentCache {
+        ComponentCache {
+            dir: dir.into(),
+            max_bytes,
+        }
+    }
+
+    /// The cache in `~/.junkyard/cache`, if there is a home folder.
+    pub fn in_home() -> Option<ComponentCache> {
+        let mut dir = env::home_dir()?;
+        dir.push(".junkyard/cache");
+        Some(ComponentCache::new(dir, DEFAULT_MAX_CACHE_BYTES))
+    }
+
+    /// Returns the compiled `wasm` component, compiling and storing it if it
+    /// is not cached yet.
+    pub fn load(&self, engine: &Engine, wasm: &[u8]) -> Result<Component> {
+        let path = self.entry_path(engine, wasm);
+        if let Some(component) = self.get(engine, &path) {
+            return Ok(component);
+        }
+        let component = Component::new(engine, wasm)?;
+        // a cache that cannot be written only costs a compilation next time
+        if let Ok(serialized) = component.serialize() {
+            if self.put(&path, &serialized).is_ok() {
+                let _ = self.evict();
+            }
+        }
+        Ok(component)
+    }
+
+    /// The disk space used by the cached components.
+    pub fn size(&self) -> io::Result<u64> {
+        Ok(self.entries()?.iter().map(|entry| entry.size).sum())
+    }
+
+    /// Removes every cached component.
+    pub fn clear(&self) -> io::Result<()> {
+        for entry in self.entries()? {
+            remove_entry(&entry.path)?;
+        }
+        Ok(())
+    }
+
+    fn entry_path(&self, engine: &Engine, wasm: &[u8]) -> PathBuf {
+        // `DefaultHasher` may change with the Rust release, which would
+        // orphan every entry
+        let mut engine_config = HashedBytes::default();
+        engine
+            .precompile_compatibility_hash()
+            .hash(&mut engine_config);
+        self.dir.join(format!(
+            "{}-{}.{}",
+            sha256::digest(wasm),
+            &sha256::digest(engine_config.0)[..16],
+            EXTENSION
+        ))
+    }
+
+    fn get(&self, engine: &Engine, path: &Path) -> Option<Component> {
+        let contents = fs::read(path).ok()?;
+        let serialized = match contents.split_at_checked(DIGEST_LEN) {
+            Some((digest, serialized)) if digest == sha256::digest(serialized).as_bytes() => {
+                serialized
+            }
+            _ => {
+                let _ = remove_entry(path);
+                return None;
+            }
+        };
+        // SAFETY: the bytes are exactly what `Component::serialize` produced
+        // for an engine with the same configuration, see `entry_path`
+        let component = unsafe { Component::deserialize(engine, serialized) }.ok()?;
+        // entries are evicted least recently used first
+        if let Ok(file) = File::options().write(true).open(path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        Some(component)
+    }
+
+    /// Writes the entry next to its final place and renames it, so that
+    /// a crash or a second junkyard never leaves a partial entry behind.
+    fn put(&self, path: &Path, serialized: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let temporary = path.with_extension(format!("{}.{}", EXTENSION, process::id()));
+        let written = File::create(&temporary).and_then(|mut file| {
+            file.write_all(sha256::digest(serialized).as_bytes())?;
+            file.write_all(serialized)?;
+            file.sync_all()
+        });
+        let renamed = written.and_then(|_| fs::rename(&temporary, path));
+        if renamed.is_err() {
+            let _ = fs::remove_file(&temporary);
+        }
+        renamed
+    }
+
+    /// Removes the least recently used entries until the cache fits in
+    /// `max_bytes`.
+    fn evict(&self) -> io::Result<()> {
+        let mut entries = self.entries()?;
+        let mut size: u64 = entries.iter().map(|entry| entry.size).sum();
+        entries.sort_by_key(|entry| entry.used);
+        for entry in entries {
+            if size <= self.max_bytes {
+                break;
+            }
+            remove_entry(&entry.path)?;
+            size -= entry.size;
+        }
+        Ok(())
+    }
+
+    fn entries(&self) -> io::Result<Vec<Entry>> {
+        let read_dir = match fs::read_dir(&self.dir) {
+            Ok(read_dir) => read_dir,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(error),
+        };
+        let mut entries = vec![];
+        for dir_entry in read_dir {
+            let path = dir_entry?.path();
+            if path
+                .extension()
+                .is_none_or(|extension| extension != EXTENSION)
+            {
+                continue;
+            }
+            let metadata = fs::metadata(&path)?;
+            entries.push(Entry {
+                size: metadata.len(),
+                used: metadata.modified()?,
+                path,
+            });
+        }
+        Ok(entries)
+    }
+}
+
+struct Entry {
+    path: PathBuf,
+    size: u64,
+    used: SystemTime,
+}
+
+/// Removes an entry, which another junkyard may have removed already.
+fn remove_entry(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env::temp_dir,
+        fs::{self, File},
+        path::{Path, PathBuf},
+        time::{Duration, SystemTime},
+    };
+
+    use wasmtime::{Config, Engine};
+
+    use super::{ComponentCache, DEFAULT_MAX_CACHE_BYTES};
+    use crate::{test_plugin::TestPlugin, PluginRuntime};
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = temp_dir().join(format!("junkyard_cache_{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn only_entry(dir: &Path) -> PathBuf {
+        let mut entries = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+        entries.pop().unwrap()
+    }
+
+    #[test]
+    fn corrupted_entries_are_compiled_again() {
+        let dir = cache_dir("corrupted");
+        let cache = ComponentCache::new(&dir, DEFAULT_MAX_CACHE_BYTES);
+        let runtime = PluginRuntime::new().unwrap();
+        let engine = runtime.engine();
+        let wasm = TestPlugin::new().build();
+
+        cache.load(engine, &wasm).unwrap();
+        let entry = only_entry(&dir);
+        let mut contents = fs::read(&entry).unwrap();
+        let last = contents.len() - 1;
+        contents[last] ^= 0xff;
+        fs::write(&entry, &contents).unwrap();
+        let corrupted = cache.get(engine, &entry).is_none();
+        cache.load(engine, &wasm).unwrap();
+        let repaired = cache.get(engine, &entry).is_some();
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(corrupted);
+        assert!(repaired);
+    }
+
+    #[test]
+    fn keyed_on_engine() {
+        let cache = ComponentCache::new(cache_dir("keyed"), DEFAULT_MAX_CACHE_BYTES);
+        let wasm = TestPlugin::new().build();
+        let runtime = PluginRuntime::new().unwrap();
+        let engine = runtime.engine();
+        let other = Engine::new(Config::new().wasm_component_model(true)).unwrap();
+
+        assert_eq!(
+            cache.entry_path(engine, &wasm),
+            cache.entry_path(PluginRuntime::new().unwrap().engine(), &wasm)
+        );
+        assert_ne!(
+            cache.entry_path(engine, &wasm),
+            cache.entry_path(&other, &wasm)
+        );
+        assert_ne!(
+            cache.entry_path(engine, &wasm),
+            cache.entry_path(engine, &TestPlugin::new().with_data(0, "a").build())
+        );
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let dir = cache_dir("evict");
+        let runtime = PluginRuntime::new().unwrap();
+        let engine = runtime.engine();
+        let first = TestPlugin::new().with_data(0, "first").build();
+        let second = TestPlugin::new().with_data(0, "second").build();
+
+        ComponentCache::new(&dir, DEFAULT_MAX_CACHE_BYTES)
+            .load(engine, &first)
+            .unwrap();
+        let entry = only_entry(&dir);
+        File::options()
+            .write(true)
+            .open(&entry)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(60))
+            .unwrap();
+        let cache = ComponentCache::new(&dir, fs::metadata(&entry).unwrap().len() + 16);
+        cache.load(engine, &second).unwrap();
+        let kept = only_entry(&dir);
+
+        cache.clear().unwrap();
+        let size = cache.size().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(kept, cache.entry_path(engine, &second));
+        assert_eq!(size, 0);
+    }
+}
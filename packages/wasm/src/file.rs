@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use vfs::{AsyncFile, Operation, Seek, VfsError, VfsResult};
+use wasmtime::component::ResourceAny;
+
+use crate::instance::{Instance, PluginPool, Retry};
+
+/// The largest amount of data moved across the plugin boundary in a single
+/// call, larger reads and writes are split by the caller.
+const MAX_CHUNK: usize = 64 * 1024;
+
+/// A file opened by a plugin, only the instance that opened it can use it.
+pub(crate) struct WasmFile {
+    plugin: Arc<PluginPool>,
+    instance: Arc<Instance>,
+    /// the instance generation that opened the file
+    generation: u64,
+    file: ResourceAny,
+    /// what calls report to, it is the one of the filesystem the file was
+    /// opened through
+    operation: Option<Arc<Operation>>,
+}
+
+impl WasmFile {
+    pub(crate) fn new(
+        plugin: Arc<PluginPool>,
+        instance: Arc<Instance>,
+        generation: u64,
+        file: ResourceAny,
+        operation: Option<Arc<Operation>>,
+    ) -> WasmFile {
+        WasmFile {
+            plugin,
+            instance,
+            generation,
+            file,
+            operation,
+        }
+    }
+}
+
+#[async_trait]
+impl AsyncFile for WasmFile {
+    async fn read(&mut self, buffer: &mut [u8]) -> VfsResult<u64> {
+        let len = buffer.len().min(MAX_CHUNK);
+        let file = self.file;
+        let data = self
+            .plugin
+            .call(
+                &self.instance,
+                Some(self.generation),
+                self.operation.as_ref(),
+                Retry::Never,
+                move |store, api, _| {
+                    Box::pin(async move { api.file().call_read(store, file, len as u64).await })
+                },
+            )
+            .await?;
+        if data.len() > len {
+            return Err(VfsError::InvalidResponse(format!(
+                "the plugin returned {} bytes, but only {} were requested",
+                data.len(),
+                len
+            )));
+        }
+        buffer[..data.len()].copy_from_slice(&data);
+        Ok(data.len() as u64)
+    }
+
+    async fn write(&mut self, buffer: &[u8]) -> VfsResult<u64> {
+        let chunk = buffer[..buffer.len().min(MAX_CHUNK)].to_vec();
+        let len = chunk.len() as u64;
+        let file = self.file;
+        let written = self
+            .plugin
+            .call(
+                &self.instance,
+                Some(self.generation),
+                self.operation.as_ref(),
+                Retry::Never,
+                move |store, api, _| {
+                    let chunk = chunk.clone();
+                    Box::pin(async move { api.file().call_write(store, file, &chunk).await })
+                },
+            )
+            .await?;
+        if written > len {
+            return Err(VfsError::InvalidResponse(format!(
+                "the plugin wrote {} bytes, but only {} were sent",
+                written, len
+            )));
+        }
+        Ok(written)
+    }
+
+    async fn seek(&mut self, from: Seek) -> VfsResult<u64> {
+        let file = self.file;
+        self.plugin
+            .call(
+                &self.instance,
+                Some(self.generation),
+                self.operation.as_ref(),
+                Retry::Never,
+                move |store, api, _| {
+                    Box::pin(async move { api.file().call_seek(store, file, from).await })
+                },
+            )
+            .await
+    }
+}
+
+impl Drop for WasmFile {
+    fn drop(&mut self) {
+        self.instance.drop_file(self.generation, self.file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vfs::{AbsolutePath, OpenOptions, Vfs, VfsError};
+    use wasm_encoder::Instruction;
+
+    use crate::{
+        test_plugin::{mem, return_slice, Body, TestPlugin, RET_AREA},
+        PluginPolicy,
+    };
+
+    #[test]
+    fn chunks_larger_than_asked() {
+        // reads 8 bytes and claims to have written 7
+        let write = vec![
+            Instruction::I32Const(RET_AREA),
+            Instruction::I32Const(0),
+            Instruction::I32Store8(mem(0, 0)),
+            Instruction::I32Const(RET_AREA),
+            Instruction::I64Const(7),
+            Instruction::I64Store(mem(8, 3)),
+            Instruction::I32Const(RET_AREA),
+        ];
+        let vfs = TestPlugin::new()
+            .with("[method]file.read", Body::Code(return_slice(1024, 8)))
+            .with("[method]file.write", Body::Code(write))
+            .with_data(1024, "junkyard")
+            .start(&PluginPolicy::default())
+            .unwrap();
+        let mut file = vfs
+            .open(&AbsolutePath::new("/file"), OpenOptions::read())
+            .unwrap();
+
+        assert!(matches!(
+            file.read(&mut [0; 4]),
+            Err(VfsError::InvalidResponse(_))
+        ));
+        assert!(matches!(
+            file.write(b"x"),
+            Err(VfsError::InvalidResponse(_))
+        ));
+    }
+}
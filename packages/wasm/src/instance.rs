@@ -0,0 +1,607 @@
+use std::{
+    future::Future,
+    mem,
+    ops::Deref,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use anyhow::{anyhow, Result};
+use tokio::sync::{self, Semaphore, SemaphorePermit};
+use vfs::{Operation, VfsError, VfsNotice, VfsResult};
+use wasm_vfs_api::{AbsolutePath, MountConfig, PluginInfo, VfsExports, VfsExportsPre};
+use wasmtime::{
+    component::{Resource, ResourceAny},
+    Store, Trap, UpdateDeadline,
+};
+
+use crate::{
+    epoch::EpochTicker, plugin_trap, policy::CapturedStdio, secret::MountSecrets, PluginPolicy,
+    PluginServices, WasmVfsState,
+};
+
+/// Whether a call may be sent again to a freshly started plugin after it
+/// crashed the previous one. Only calls that do not change anything can.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Retry {
+    Never,
+    Once,
+}
+
+/// What a call into the plugin returns, a trap is an `Err` of the outer
+/// result.
+pub(crate) type CallFuture<'a, T> =
+    Pin<Box<dyn Future<Output = wasmtime::Result<VfsResult<T>>> + Send + 'a>>;
+
+struct Running {
+    store: Store<WasmVfsState>,
+    bindings: VfsExports,
+    filesystem: ResourceAny,
+}
+
+/// One instance of a plugin, in a store of its own.
+pub(crate) struct Instance {
+    state: sync::Mutex<InstanceState>,
+    /// files closed while the instance was busy, they are released before
+    /// its next call
+    dropped_files: Mutex<Vec<(u64, ResourceAny)>>,
+}
+
+struct InstanceState {
+    /// `None` if the instance could not be started again
+    running: Option<Running>,
+    /// incremented on every restart, files opened by a previous
+    /// generation are gone
+    generation: u64,
+}
+
+impl Instance {
+    fn new(running: Running) -> Instance {
+        Instance {
+            state: sync::Mutex::new(InstanceState {
+                running: Some(running),
+                generation: 0,
+            }),
+            dropped_files: Mutex::new(vec![]),
+        }
+    }
+
+    pub(crate) async fn generation(&self) -> u64 {
+        self.state.lock().await.generation
+    }
+
+    /// Releases a file resource on the next call, unless the instance that
+    /// owned it was restarted by then.
+    pub(crate) fn drop_file(&self, generation: u64, file: ResourceAny) {
+        lock(&self.dropped_files).push((generation, file));
+    }
+}
+
+struct Health {
+    /// crashes since the last successful call, in any instance
+    crashes: u32,
+    disabled: bool,
+    notices: Vec<VfsNotice>,
+}
+
+/// The instances of a plugin, shared between a [`crate::WasmVfs`] and the
+/// files opened through it.
+///
+/// Calls run in parallel, each in an idle instance, and new instances are
+/// started while all are busy, up to `concurrent_calls`. An instance that
+/// traps is started again on a fresh store, until the plugin crashes
+/// `max_restarts` times in a row and is disabled.
+pub(crate) struct PluginPool {
+    pre: VfsExportsPre<WasmVfsState>,
+    policy: PluginPolicy,
+    /// what every instance mounts when it starts
+    config: MountConfig,
+    captured: Option<CapturedStdio>,
+    services: PluginServices,
+    /// kept by the mount, the secrets the user asked to remember outlive a
+    /// reload of the plugin
+    pub(crate) secrets: Arc<MountSecrets>,
+    /// epoch ticks a single call may take
+    deadline: u64,
+    idle: Mutex<Vec<Arc<Instance>>>,
+    /// one permit for every instance the pool may run
+    permits: Semaphore,
+    health: Mutex<Health>,
+    _ticker: Arc<EpochTicker>,
+}
+
+impl PluginPool {
+    pub(crate) async fn start(
+        pre: VfsExportsPre<WasmVfsState>,
+        policy: PluginPolicy,
+        config: MountConfig,
+        services: PluginServices,
+        secrets: Arc<MountSecrets>,
+        deadline: u64,
+        ticker: Arc<EpochTicker>,
+    ) -> Result<PluginPool> {
+        let pool = PluginPool {
+            pre,
+            captured: policy.captured_stdio(),
+            secrets,
+            config,
+            services,
+            permits: Semaphore::new(policy.limits.concurrent_calls.max(1) as usize),
+            policy,
+            deadline,
+            idle: Mutex::new(vec![]),
+            health: Mutex::new(Health {
+                crashes: 0,
+                disabled: false,
+                notices: vec![],
+            }),
+            _ticker: ticker,
+        };
+        // the first instance is started right away, a plugin that cannot
+        // start fails to load
+        let instance = Arc::new(Instance::new(pool.instantiate().await?));
+        lock(&pool.idle).push(instance);
+        Ok(pool)
+    }
+
+    async fn instantiate(&self) -> Result<Running> {
+        let state = WasmVfsState::new(
+            &self.policy,
+            self.captured.as_ref(),
+            &self.services,
+            &self.secrets,
+        )?;
+        let mut store = Store::new(self.pre.engine(), state);
+        store.limiter(|state| &mut state.limiter);
+        store.set_epoch_deadline(self.deadline);
+        let deadline = self.deadline;
+        store.epoch_deadline_callback(move |mut store| {
+            let state = store.data_mut();
+            if mem::take(&mut state.renew_deadline) {
+                Ok(UpdateDeadline::Continue(deadline))
+            } else {
+                Err(Trap::Interrupt.into())
+            }
+        });
+        let bindings = self.pre.instantiate_async(&mut store).await?;
+        let filesystem = bindings
+            .call_init(&mut store, &self.config)
+            .await?
+            .map_err(|error| {
+                anyhow!(
+                    "the plugin failed to mount {}: {}",
+                    self.config.uri,
+                    error.message()
+                )
+            })?;
+        Ok(Running {
+            store,
+            bindings,
+            filesystem,
+        })
+    }
+
+    pub(crate) fn captured(&self) -> Option<&CapturedStdio> {
+        self.captured.as_ref()
+    }
+
+    pub(crate) fn take_notices(&self) -> Vec<VfsNotice> {
+        mem::take(&mut lock(&self.health).notices)
+    }
+
+    /// Asks the plugin to describe itself.
+    pub(crate) async fn info(&self) -> VfsResult<PluginInfo> {
+        let instance = self.lease().await?;
+        self.call(&instance, None, None, Retry::Once, |store, api, _| {
+            Box::pin(async move { Ok(Ok(api.call_info(store).await?)) })
+        })
+        .await
+    }
+
+    /// Takes an idle instance, starts a new one if all of them are busy, or
+    /// waits for one to be released once the pool is full.
+    pub(crate) async fn lease(&self) -> VfsResult<Lease<'_>> {
+        let permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|error| VfsError::PluginTrap(error.to_string()))?;
+        let idle = lock(&self.idle).pop();
+        let instance = match idle {
+            Some(instance) => instance,
+            None => Arc::new(Instance::new(
+                self.instantiate().await.map_err(plugin_trap)?,
+            )),
+        };
+        Ok(Lease {
+            pool: self,
+            instance,
+            _permit: permit,
+        })
+    }
+
+    /// Replaces a crashed instance with a fresh one, or disables the plugin
+    /// if it crashed too often.
+    async fn restart(&self, state: &mut InstanceState, error: &VfsError) {
+        state.running = None;
+        state.generation += 1;
+        let reason = error.message();
+        {
+            let mut health = lock(&self.health);
+            health.crashes += 1;
+            if health.crashes > self.policy.limits.max_restarts {
+                health.disabled = true;
+                health.notices.push(VfsNotice::Disabled { reason });
+                return;
+            }
+        }
+        let running = self.instantiate().await;
+        let mut health = lock(&self.health);
+        match running {
+            Ok(running) => {
+                state.running = Some(running);
+                health.notices.push(VfsNotice::Restarted { reason });
+            }
+            Err(error) => {
+                health.disabled = true;
+                health.notices.push(VfsNotice::Disabled {
+                    reason: format!("{}, restarting failed: {}", reason, error),
+                });
+            }
+        }
+    }
+
+    /// Calls into `instance`, restarting it if the call traps. Calls made
+    /// for files have to pass the `generation` the file was opened in. The
+    /// plugin reports its progress to `operation`, and the call is not
+    /// made if it was cancelled already.
+    pub(crate) async fn call<T>(
+        &self,
+        instance: &Instance,
+        generation: Option<u64>,
+        operation: Option<&Arc<Operation>>,
+        retry: Retry,
+        call: impl for<'a> Fn(
+                &'a mut Store<WasmVfsState>,
+                &'a VfsExports,
+                ResourceAny,
+            ) -> CallFuture<'a, T>
+            + Send
+            + Sync,
+    ) -> VfsResult<T> {
+        let mut state = instance.state.lock().await;
+        self.release_dropped_files(instance, &mut state).await;
+        if generation.is_some_and(|generation| generation != state.generation) {
+            return Err(VfsError::PluginTrap(
+                "the plugin was restarted and the file was closed".to_string(),
+            ));
+        }
+        let mut attempts = match retry {
+            Retry::Never => 1,
+            Retry::Once => 2,
+        };
+        loop {
+            let disabled = lock(&self.health).disabled;
+            let Some(Running {
+                store,
+                bindings,
+                filesystem,
+            }) = state.running.as_mut().filter(|_| !disabled)
+            else {
+                return Err(VfsError::PluginTrap(
+                    "the plugin crashed too many times and was disabled".to_string(),
+                ));
+            };
+            if operation.is_some_and(|operation| operation.is_cancelled()) {
+                return Err(VfsError::Cancelled);
+            }
+            store.set_epoch_deadline(self.deadline);
+            store.data_mut().operation = operation.cloned();
+            let ret = call(store, bindings, *filesystem).await;
+            store.data_mut().operation = None;
+            match ret {
+                Ok(result) => {
+                    lock(&self.health).crashes = 0;
+                    return result;
+                }
+                Err(error) => {
+                    let error = plugin_trap(error);
+                    self.restart(&mut state, &error).await;
+                    attempts -= 1;
+                    // a call that timed out or hit a limit would do the same
+                    // again, so it is not worth retrying
+                    if attempts == 0 || !matches!(error, VfsError::PluginTrap(_)) {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+    }
+
+    async fn release_dropped_files(&self, instance: &Instance, state: &mut InstanceState) {
+        let dropped = mem::take(&mut *lock(&instance.dropped_files));
+        for (generation, file) in dropped {
+            // a restart closes the files that were left
+            if generation != state.generation {
+                continue;
+            }
+            let Some(running) = state.running.as_mut() else {
+                return;
+            };
+            running.store.set_epoch_deadline(self.deadline);
+            // the crash is not left to the call that follows, which may not
+            // be retried
+            if let Err(error) = file.resource_drop_async(&mut running.store).await {
+                self.restart(state, &plugin_trap(error)).await;
+            }
+        }
+    }
+
+    /// Same as [`PluginPool::call`] on an idle instance, but lends `path` to
+    /// the plugin as a borrowed `absolute-path` resource for the duration of
+    /// the call.
+    pub(crate) async fn call_with_path<T>(
+        &self,
+        path: &AbsolutePath,
+        operation: Option<&Arc<Operation>>,
+        retry: Retry,
+        call: impl for<'a> Fn(
+                &'a mut Store<WasmVfsState>,
+                &'a VfsExports,
+                ResourceAny,
+                Resource<AbsolutePath>,
+            ) -> CallFuture<'a, T>
+            + Send
+            + Sync
+            + 'static,
+    ) -> VfsResult<T> {
+        let instance = self.lease().await?;
+        self.call_with_path_on(&instance, path, operation, retry, call)
+            .await
+    }
+
+    pub(crate) async fn call_with_path_on<T>(
+        &self,
+        instance: &Instance,
+        path: &AbsolutePath,
+        operation: Option<&Arc<Operation>>,
+        retry: Retry,
+        call: impl for<'a> Fn(
+                &'a mut Store<WasmVfsState>,
+                &'a VfsExports,
+                ResourceAny,
+                Resource<AbsolutePath>,
+            ) -> CallFuture<'a, T>
+            + Send
+            + Sync
+            + 'static,
+    ) -> VfsResult<T> {
+        // the call outlives this frame inside the future, so it is shared
+        let call = Arc::new(call);
+        self.call(
+            instance,
+            None,
+            operation,
+            retry,
+            |store, api, filesystem| {
+                let path = path.clone();
+                let call = call.clone();
+                Box::pin(async move {
+                    let path = store.data_mut().create_absolute_path_resource(path)?;
+                    let ret = call(
+                        &mut *store,
+                        api,
+                        filesystem,
+                        Resource::<AbsolutePath>::new_borrow(path.rep()),
+                    )
+                    .await;
+                    store.data_mut().take_absolute_path(path);
+                    ret
+                })
+            },
+        )
+        .await
+    }
+}
+
+/// An instance taken from a [`PluginPool`], it goes back to the pool when
+/// the lease is dropped.
+pub(crate) struct Lease<'a> {
+    pool: &'a PluginPool,
+    instance: Arc<Instance>,
+    _permit: SemaphorePermit<'a>,
+}
+
+impl Lease<'_> {
+    pub(crate) fn instance(&self) -> Arc<Instance> {
+        self.instance.clone()
+    }
+}
+
+impl Deref for Lease<'_> {
+    type Target = Instance;
+
+    fn deref(&self) -> &Instance {
+        &self.instance
+    }
+}
+
+impl Drop for Lease<'_> {
+    fn drop(&mut self) {
+        // the permit is released after the instance is back
+        lock(&self.pool.idle).push(self.instance.clone());
+    }
+}
+
+/// Locks a mutex even if a thread panicked while holding it, the pool's
+/// state stays consistent between statements.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use vfs::{block_on, AbsolutePath, OpenOptions, Seek, Vfs, VfsError, VfsNotice};
+    use wasm_encoder::Instruction;
+
+    use super::lock;
+    use crate::{
+        test_plugin::{mem, return_string, Body, TestPlugin, RET_AREA},
+        PluginLimits, PluginPolicy,
+    };
+
+    fn plugin() -> TestPlugin {
+        TestPlugin::new()
+            .with("[method]filesystem.stat", Body::Trap)
+            .with("[method]filesystem.unlink", Body::Trap)
+            .with("[method]filesystem.read-link", return_string(1024, 6))
+            .with_data(1024, "target")
+    }
+
+    fn restarts(notices: &[VfsNotice]) -> usize {
+        notices
+            .iter()
+            .filter(|notice| matches!(notice, VfsNotice::Restarted { .. }))
+            .count()
+    }
+
+    #[test]
+    fn restart_after_trap() {
+        let vfs = plugin().start(&PluginPolicy::default()).unwrap();
+        let path = AbsolutePath::new("/link");
+
+        assert!(matches!(vfs.stat(&path), Err(VfsError::PluginTrap(_))));
+        // stat is retried once on the restarted plugin
+        assert_eq!(restarts(&vfs.take_notices()), 2);
+        assert_eq!(vfs.read_link(&path), Ok("target".to_string()));
+
+        assert!(matches!(vfs.unlink(&path), Err(VfsError::PluginTrap(_))));
+        assert_eq!(restarts(&vfs.take_notices()), 1);
+        assert_eq!(vfs.read_link(&path), Ok("target".to_string()));
+        assert!(vfs.take_notices().is_empty());
+    }
+
+    #[test]
+    fn disable_after_repeated_crashes() {
+        let policy = PluginPolicy {
+            limits: PluginLimits {
+                max_restarts: 2,
+                ..PluginLimits::default()
+            },
+            ..PluginPolicy::default()
+        };
+        let vfs = plugin().start(&policy).unwrap();
+        let path = AbsolutePath::new("/link");
+
+        let _ = vfs.stat(&path);
+        let _ = vfs.stat(&path);
+        let notices = vfs.take_notices();
+
+        assert_eq!(restarts(&notices), 2);
+        assert!(matches!(notices.last(), Some(VfsNotice::Disabled { .. })));
+        assert!(matches!(vfs.read_link(&path), Err(VfsError::PluginTrap(_))));
+    }
+
+    #[test]
+    fn files_are_closed_by_restart() {
+        let vfs = plugin().start(&PluginPolicy::default()).unwrap();
+        let path = AbsolutePath::new("/file");
+
+        let mut file = vfs.open(&path, OpenOptions::read()).unwrap();
+        let _ = vfs.stat(&path);
+
+        assert!(matches!(
+            file.seek(Seek::Start(0)),
+            Err(VfsError::PluginTrap(_))
+        ));
+    }
+
+    #[test]
+    fn restart_after_dropping_a_file() {
+        let vfs = plugin()
+            .with("[dtor]file", Body::Trap)
+            .start(&PluginPolicy::default())
+            .unwrap();
+        let path = AbsolutePath::new("/file");
+
+        drop(vfs.open(&path, OpenOptions::read()).unwrap());
+        // opening is not retried, it runs on the restarted plugin
+        assert!(vfs.open(&path, OpenOptions::read()).is_ok());
+        assert_eq!(restarts(&vfs.take_notices()), 1);
+    }
+
+    #[test]
+    fn restart_after_timeout() {
+        let policy = PluginPolicy {
+            limits: PluginLimits {
+                call_timeout_ms: 50,
+                ..PluginLimits::default()
+            },
+            ..PluginPolicy::default()
+        };
+        let vfs = plugin()
+            .with("[method]filesystem.stat", Body::Loop)
+            .start(&policy)
+            .unwrap();
+        let path = AbsolutePath::new("/link");
+
+        assert_eq!(vfs.stat(&path), Err(VfsError::Timeout));
+        // timeouts are not retried
+        assert_eq!(restarts(&vfs.take_notices()), 1);
+        assert_eq!(vfs.read_link(&path), Ok("target".to_string()));
+    }
+
+    fn concurrent(calls: u32) -> PluginPolicy {
+        PluginPolicy {
+            limits: PluginLimits {
+                concurrent_calls: calls,
+                ..PluginLimits::default()
+            },
+            ..PluginPolicy::default()
+        }
+    }
+
+    #[test]
+    fn busy_instances_are_not_shared() {
+        let vfs = plugin().start(&concurrent(2)).unwrap();
+        let path = AbsolutePath::new("/link");
+        let pool = vfs.plugin();
+
+        // with a single instance this would wait for the lease forever
+        let busy = block_on(pool.lease()).unwrap();
+        assert_eq!(vfs.read_link(&path), Ok("target".to_string()));
+        drop(busy);
+        assert_eq!(vfs.read_link(&path), Ok("target".to_string()));
+
+        assert_eq!(lock(&pool.idle).len(), 2);
+    }
+
+    #[test]
+    fn files_stay_on_their_instance() {
+        let vfs = plugin()
+            .with(
+                "[method]file.seek",
+                Body::Code(vec![
+                    Instruction::I32Const(RET_AREA),
+                    Instruction::I32Const(0),
+                    Instruction::I32Store8(mem(0, 0)),
+                    Instruction::I32Const(RET_AREA),
+                    Instruction::I64Const(7),
+                    Instruction::I64Store(mem(8, 3)),
+                    Instruction::I32Const(RET_AREA),
+                ]),
+            )
+            .start(&concurrent(2))
+            .unwrap();
+        let path = AbsolutePath::new("/file");
+
+        let mut file = vfs.open(&path, OpenOptions::read()).unwrap();
+        // the file's instance is busy, so stat crashes the other one
+        let pool = vfs.plugin();
+        let busy = block_on(pool.lease()).unwrap();
+        assert!(matches!(vfs.stat(&path), Err(VfsError::PluginTrap(_))));
+        drop(busy);
+
+        assert_eq!(file.seek(Seek::Start(7)), Ok(7));
+    }
+}
@@ -1,41 +1,110 @@
 use anyhow::Result;
-use std::sync::MutexGuard;
-use std::{env, fs, sync::Mutex};
-use vfs::Vfs;
+use async_trait::async_trait;
+use file::WasmFile;
+use instance::{PluginPool, Retry};
+use limits::PluginLimiter;
+use log::PluginLogger;
+use plugin::Mount;
+use policy::CapturedStdio;
+use secret::{MountSecrets, PromptSlot};
+use std::sync::Arc;
+use storage::PluginStorage;
+use vfs::{
+    block_on, AsyncFile, AsyncVfs, BlockingFile, DirEntry, File, OpenOptions, Operation, Progress,
+    Stat, Vfs, VfsError, VfsNotice, VfsResult,
+};
 use wasm_vfs_api::{
+    exports::junkyard_vfs::vfs_plugin::vfs::OpenFlags,
     junkyard_vfs::vfs_plugin::vfs_host::{Host, HostAbsolutePath},
-    VfsPlugin, AbsolutePath
+    AbsolutePath, StorageError,
 };
-use wasmtime::{
-    component::{Component, Linker, Resource, ResourceAny},
-    Config, Engine, Store,
+use wasmtime::{component::Resource, Trap};
+use wasmtime_wasi::{ResourceTable, WasiCtx, WasiView};
+
+mod cache;
+mod epoch;
+mod file;
+mod instance;
+mod limits;
+mod log;
+mod plugin;
+mod policy;
+mod registry;
+mod response;
+mod runtime;
+mod secret;
+mod signature;
+mod storage;
+#[cfg(test)]
+mod test_plugin;
+
+pub use cache::{ComponentCache, DEFAULT_MAX_CACHE_BYTES};
+pub use limits::LimitExceeded;
+pub use log::{LogRecord, PluginLog, DEFAULT_LOG_CAPACITY};
+pub use plugin::{StartedMounts, WasmPlugin};
+pub use policy::{PluginLimits, PluginPolicy, Preopen, Stdio};
+pub use registry::{
+    Permission, PluginManifest, PluginRegistry, RegisteredPlugin, SYSTEM_PLUGINS_DIR,
 };
-use wasmtime_wasi::{DirPerms, FilePerms, ResourceTable, WasiCtx, WasiView};
+pub use runtime::PluginRuntime;
+pub use secret::{Secret, SecretPrompt, SecretRequest};
+pub use signature::{SignaturePolicy, TrustedKeys, SIGNATURE_SECTION};
+pub use wasm_vfs_api::{Capability, LogLevel, MountConfig, PluginInfo};
+
+/// What the host offers a plugin besides WASI, shared by all its mounts.
+#[derive(Clone)]
+struct PluginServices {
+    logger: PluginLogger,
+    storage: Arc<PluginStorage>,
+    prompt: PromptSlot,
+}
 
 struct WasmVfsState {
     ctx: WasiCtx,
     table: ResourceTable,
+    limiter: PluginLimiter,
+    services: PluginServices,
+    secrets: Arc<MountSecrets>,
+    /// what the current call reports to, if anything
+    operation: Option<Arc<Operation>>,
+    /// whether the plugin waited for the user or reported progress since
+    /// its deadline was set, which gives it a new deadline
+    renew_deadline: bool,
 }
 
 impl WasmVfsState {
-    fn new(root: &AbsolutePath) -> WasmVfsState {
-        WasmVfsState {
-            ctx: WasiCtx::builder()
-                .inherit_stdio()
-                .inherit_args()
-                .preopened_dir(root.as_str(), "/", DirPerms::READ, FilePerms::READ)
-                .unwrap()
-                .build(),
+    fn new(
+        policy: &PluginPolicy,
+        captured: Option<&CapturedStdio>,
+        services: &PluginServices,
+        secrets: &Arc<MountSecrets>,
+    ) -> Result<WasmVfsState> {
+        Ok(WasmVfsState {
+            ctx: policy.build(captured, &services.logger)?,
             table: ResourceTable::new(),
-        }
+            limiter: PluginLimiter::new(&policy.limits),
+            services: services.clone(),
+            secrets: secrets.clone(),
+            operation: None,
+            renew_deadline: false,
+        })
     }
 
-    fn create_absolute_path_resource(&mut self, path: AbsolutePath) -> Resource<AbsolutePath> {
-        self.table.push(path).unwrap()
+    fn create_absolute_path_resource(
+        &mut self,
+        path: AbsolutePath,
+    ) -> wasmtime::Result<Resource<AbsolutePath>> {
+        self.limiter.acquire_host_resource()?;
+        self.table.push(path).map_err(|error| {
+            self.limiter.release_host_resource();
+            error.into()
+        })
     }
 
     fn take_absolute_path(&mut self, path: Resource<AbsolutePath>) -> Option<AbsolutePath> {
-        self.table.delete(path).ok()
+        let path = self.table.delete(path).ok()?;
+        self.limiter.release_host_resource();
+        Some(path)
     }
 }
 
@@ -48,59 +117,65 @@ impl WasiView for WasmVfsState {
     }
 }
 
+/// A mounted filesystem of a plugin. It keeps working when the plugin is
+/// reloaded, the calls made from then on go to the new version.
 pub struct WasmVfs {
-    store: Mutex<Store<WasmVfsState>>,
-    instance: VfsPlugin,
-    vfs_plugin: ResourceAny,
+    mount: Arc<Mount>,
+    /// what calls report to, see [`AsyncVfs::with_operation`]
+    operation: Option<Arc<Operation>>,
 }
 
 impl WasmVfs {
-    fn get_store(&self) -> MutexGuard<'_, Store<WasmVfsState>> {
-        self.store.lock().unwrap_or_else(|s| s.into_inner())
+    fn plugin(&self) -> Arc<PluginPool> {
+        self.mount.current().plugin.clone()
+    }
+
+    /// What the plugin said about itself when it was loaded, or reloaded.
+    pub fn info(&self) -> PluginInfo {
+        self.mount.current().info.clone()
+    }
+
+    /// What the plugin wrote to its standard output, if its policy captures
+    /// stdio.
+    pub fn stdout(&self) -> Option<Vec<u8>> {
+        Some(self.plugin().captured()?.stdout.contents().to_vec())
+    }
+
+    /// What the plugin wrote to its standard error, if its policy captures
+    /// stdio.
+    pub fn stderr(&self) -> Option<Vec<u8>> {
+        Some(self.plugin().captured()?.stderr.contents().to_vec())
     }
 }
 
 impl HostAbsolutePath for WasmVfsState {
-    fn components(&mut self, self_: Resource<AbsolutePath>) -> Vec<String> {
-        self.table.get(&self_)
-            .as_ref()
-            .map(|path| {
-                path.components()
-                    .into_iter()
-                    .map(|s| s.to_string())
-                    .collect()
-            })
-            .unwrap()
+    fn components(&mut self, self_: Resource<AbsolutePath>) -> wasmtime::Result<Vec<String>> {
+        let path = self.table.get(&self_)?;
+        Ok(path
+            .components()
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect())
     }
 
-    fn is_root(&mut self, self_: Resource<AbsolutePath>) -> bool {
-        self.table.get(&self_)
-            .as_ref()
-            .map(|path| path.is_root())
-            .unwrap()
+    fn is_root(&mut self, self_: Resource<AbsolutePath>) -> wasmtime::Result<bool> {
+        Ok(self.table.get(&self_)?.is_root())
     }
 
-    fn parent(&mut self, self_: Resource<AbsolutePath>) -> Resource<AbsolutePath> {
-        self.create_absolute_path_resource(
-            self.table.get(&self_)
-                .as_ref()
-                .map(|path| path.parent())
-                .unwrap(),
-        )
+    fn parent(
+        &mut self,
+        self_: Resource<AbsolutePath>,
+    ) -> wasmtime::Result<Resource<AbsolutePath>> {
+        let parent = self.table.get(&self_)?.parent();
+        self.create_absolute_path_resource(parent)
     }
 
-    fn file_name(&mut self, self_: Resource<AbsolutePath>) -> String {
-        self.table.get(&self_)
-            .as_ref()
-            .map(|path| path.name().to_string())
-            .unwrap()
+    fn file_name(&mut self, self_: Resource<AbsolutePath>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&self_)?.name().to_string())
     }
 
-    fn path(&mut self, self_: Resource<AbsolutePath>) -> String {
-        self.table.get(&self_)
-            .as_ref()
-            .map(|path| path.path().to_string())
-            .unwrap()
+    fn path(&mut self, self_: Resource<AbsolutePath>) -> wasmtime::Result<String> {
+        Ok(self.table.get(&self_)?.path().to_string())
     }
 
     fn drop(&mut self, rep: Resource<AbsolutePath>) -> wasmtime::Result<()> {
@@ -110,102 +185,611 @@ impl HostAbsolutePath for WasmVfsState {
 }
 
 impl Host for WasmVfsState {
-    fn create_absolute_path(&mut self, s: String) -> Resource<AbsolutePath> {
+    fn create_absolute_path(&mut self, s: String) -> wasmtime::Result<Resource<AbsolutePath>> {
         self.create_absolute_path_resource(AbsolutePath::new(s))
     }
+
+    fn log(&mut self, level: LogLevel, message: String) -> wasmtime::Result<()> {
+        self.services.logger.log(level, &message);
+        Ok(())
+    }
+
+    fn storage_get(&mut self, key: String) -> wasmtime::Result<Option<Vec<u8>>> {
+        Ok(self.services.storage.get(&key))
+    }
+
+    fn storage_set(
+        &mut self,
+        key: String,
+        value: Vec<u8>,
+    ) -> wasmtime::Result<Result<(), StorageError>> {
+        Ok(self.services.storage.set(&key, &value))
+    }
+
+    fn storage_delete(&mut self, key: String) -> wasmtime::Result<Result<(), StorageError>> {
+        Ok(self.services.storage.delete(&key))
+    }
+
+    fn storage_list(&mut self) -> wasmtime::Result<Vec<String>> {
+        Ok(self.services.storage.list())
+    }
+
+    async fn prompt_secret(
+        &mut self,
+        title: String,
+        description: String,
+    ) -> wasmtime::Result<Option<String>> {
+        let secrets = self.secrets.clone();
+        let secret = secrets.prompt(title, description).await;
+        self.renew_deadline = true;
+        Ok(secret)
+    }
+
+    fn report_progress(&mut self, done: u64, total: u64, message: String) -> wasmtime::Result<()> {
+        if let Some(operation) = &self.operation {
+            operation.report(Progress {
+                done,
+                total,
+                message,
+            });
+        }
+        self.renew_deadline = true;
+        Ok(())
+    }
+
+    fn is_cancelled(&mut self) -> wasmtime::Result<bool> {
+        Ok(self
+            .operation
+            .as_ref()
+            .is_some_and(|operation| operation.is_cancelled()))
+    }
 }
 
-impl Vfs for WasmVfs {
-    fn open(
+pub(crate) fn plugin_trap(error: wasmtime::Error) -> VfsError {
+    match error.downcast_ref::<Trap>() {
+        Some(Trap::Interrupt) => VfsError::Timeout,
+        _ => match error.downcast_ref::<LimitExceeded>() {
+            Some(limit) => VfsError::LimitExceeded(limit.to_string()),
+            None => VfsError::PluginTrap(error.to_string()),
+        },
+    }
+}
+
+fn open_flags(open_options: OpenOptions) -> OpenFlags {
+    [
+        (open_options.create, OpenFlags::CREATE),
+        (open_options.read, OpenFlags::READ),
+        (open_options.write, OpenFlags::WRITE),
+        (open_options.truncate, OpenFlags::TRUNCATE),
+        (open_options.append, OpenFlags::APPEND),
+    ]
+    .into_iter()
+    .filter(|(enabled, _)| *enabled)
+    .fold(OpenFlags::empty(), |flags, (_, flag)| flags | flag)
+}
+
+#[async_trait]
+impl AsyncVfs for WasmVfs {
+    async fn open(
         &self,
-        _path: &AbsolutePath,
-        _open_options: vfs::OpenOptions,
-    ) -> vfs::VfsResult<Box<dyn vfs::File>> {
-        todo!()
-    }
-
-    fn unlink(&self, _path: &AbsolutePath) -> vfs::VfsResult<()> {
-        todo!()
-    }
-
-    fn stat(&self, path: &AbsolutePath) -> vfs::VfsResult<vfs::Stat> {
-        let mut store = self.get_store();
-        let path = store.data_mut().create_absolute_path_resource(path.clone());
-        let ret = self
-            .instance
-            .junkyard_vfs_vfs_plugin_vfs()
-            .filesystem()
-            .call_stat(
-                &mut *store,
-                self.vfs_plugin,
-                Resource::<AbsolutePath>::new_borrow(path.rep())
-            );
-        store.data_mut().take_absolute_path(path);
-        Ok(ret.map_err(|e| e.to_string())??)
-    }
-
-    fn read_dir(&self, path: &AbsolutePath) -> vfs::VfsResult<Vec<AbsolutePath>> {
-        let mut store = self.get_store();
-        let path = store.data_mut().create_absolute_path_resource(path.clone());
-        let ret = self
-            .instance
-            .junkyard_vfs_vfs_plugin_vfs()
-            .filesystem()
-            .call_read_dir(
-                &mut *store,
-                self.vfs_plugin,
-                Resource::<AbsolutePath>::new_borrow(path.rep()),
-            );
-        store.data_mut().take_absolute_path(path);
-        Ok(ret
-            .map_err(|e| e.to_string())??
-            .into_iter()
-            .map(|s| store.data_mut().take_absolute_path(s).unwrap())
-            .collect())
+        path: &AbsolutePath,
+        open_options: OpenOptions,
+    ) -> VfsResult<Box<dyn AsyncFile>> {
+        // the file stays with this version of the plugin if it is reloaded
+        let plugin = self.plugin();
+        let instance = plugin.lease().await?;
+        // the generation has to be read before the call, if the instance
+        // restarts during the call the file belongs to the crashed one
+        let generation = instance.generation().await;
+        let file = plugin
+            .call_with_path_on(
+                &instance,
+                path,
+                self.operation.as_ref(),
+                Retry::Never,
+                move |store, api, filesystem, path| {
+                    Box::pin(async move {
+                        api.filesystem()
+                            .call_open(store, filesystem, path, open_flags(open_options))
+                            .await
+                    })
+                },
+            )
+            .await?;
+        Ok(Box::new(WasmFile::new(
+            plugin.clone(),
+            instance.instance(),
+            generation,
+            file,
+            self.operation.clone(),
+        )))
     }
 
-    fn create_dir(&self, _path: &AbsolutePath) -> vfs::VfsResult<()> {
-        todo!()
+    async fn unlink(&self, path: &AbsolutePath) -> VfsResult<()> {
+        self.plugin()
+            .call_with_path(
+                path,
+                self.operation.as_ref(),
+                Retry::Never,
+                |store, api, filesystem, path| {
+                    Box::pin(
+                        async move { api.filesystem().call_unlink(store, filesystem, path).await },
+                    )
+                },
+            )
+            .await
     }
 
-    fn create_dir_all(&self, _path: &AbsolutePath) -> vfs::VfsResult<()> {
-        todo!()
+    async fn stat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+        self.plugin()
+            .call_with_path(
+                path,
+                self.operation.as_ref(),
+                Retry::Once,
+                |store, api, filesystem, path| {
+                    Box::pin(
+                        async move { api.filesystem().call_stat(store, filesystem, path).await },
+                    )
+                },
+            )
+            .await
     }
 
-    fn rename(&self, _from: &AbsolutePath, _to: &AbsolutePath) -> vfs::VfsResult<()> {
-        todo!()
+    async fn read_dir(&self, path: &AbsolutePath) -> VfsResult<Vec<AbsolutePath>> {
+        let entries = self
+            .plugin()
+            .call_with_path(
+                path,
+                self.operation.as_ref(),
+                Retry::Once,
+                |store, api, filesystem, path| {
+                    Box::pin(async move {
+                        let entries = match api
+                            .filesystem()
+                            .call_read_dir(&mut *store, filesystem, path)
+                            .await?
+                        {
+                            Ok(entries) => entries,
+                            Err(error) => return Ok(Err(error)),
+                        };
+                        // every handle is taken back before checking any of them,
+                        // so that none of them stays in the table
+                        let entries = entries
+                            .into_iter()
+                            .map(|entry| store.data_mut().take_absolute_path(entry))
+                            .collect::<Vec<_>>();
+                        Ok(entries.into_iter().collect::<Option<Vec<_>>>().ok_or(
+                            VfsError::InvalidResponse("unknown path resource".to_string()),
+                        ))
+                    })
+                },
+            )
+            .await?;
+        response::check_children(path, &entries)?;
+        Ok(entries)
     }
+
+    async fn read_dir_with_stat(&self, path: &AbsolutePath) -> VfsResult<Vec<DirEntry>> {
+        let entries = self
+            .plugin()
+            .call_with_path(
+                path,
+                self.operation.as_ref(),
+                Retry::Once,
+                |store, api, filesystem, path| {
+                    Box::pin(async move {
+                        api.filesystem()
+                            .call_read_dir_with_stat(store, filesystem, path)
+                            .await
+                    })
+                },
+            )
+            .await?;
+        response::check_names(&entries)?;
+        Ok(entries)
+    }
+
+    async fn create_dir(&self, path: &AbsolutePath) -> VfsResult<()> {
+        self.plugin()
+            .call_with_path(
+                path,
+                self.operation.as_ref(),
+                Retry::Never,
+                |store, api, filesystem, path| {
+                    Box::pin(async move {
+                        api.filesystem()
+                            .call_create_dir(store, filesystem, path)
+                            .await
+                    })
+                },
+            )
+            .await
+    }
+
+    async fn create_dir_all(&self, path: &AbsolutePath) -> VfsResult<()> {
+        self.plugin()
+            .call_with_path(
+                path,
+                self.operation.as_ref(),
+                Retry::Never,
+                |store, api, filesystem, path| {
+                    Box::pin(async move {
+                        api.filesystem()
+                            .call_create_dir_all(store, filesystem, path)
+                            .await
+                    })
+                },
+            )
+            .await
+    }
+
+    async fn lstat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+        self.plugin()
+            .call_with_path(
+                path,
+                self.operation.as_ref(),
+                Retry::Once,
+                |store, api, filesystem, path| {
+                    Box::pin(
+                        async move { api.filesystem().call_lstat(store, filesystem, path).await },
+                    )
+                },
+            )
+            .await
+    }
+
+    async fn read_link(&self, path: &AbsolutePath) -> VfsResult<String> {
+        self.plugin()
+            .call_with_path(
+                path,
+                self.operation.as_ref(),
+                Retry::Once,
+                |store, api, filesystem, path| {
+                    Box::pin(async move {
+                        api.filesystem()
+                            .call_read_link(store, filesystem, path)
+                            .await
+                    })
+                },
+            )
+            .await
+    }
+
+    async fn create_symlink(&self, target: &str, link: &AbsolutePath) -> VfsResult<()> {
+        let target = target.to_string();
+        self.plugin()
+            .call_with_path(
+                link,
+                self.operation.as_ref(),
+                Retry::Never,
+                move |store, api, filesystem, link| {
+                    let target = target.clone();
+                    Box::pin(async move {
+                        api.filesystem()
+                            .call_create_symlink(store, filesystem, &target, link)
+                            .await
+                    })
+                },
+            )
+            .await
+    }
+
+    async fn rename(&self, from: &AbsolutePath, to: &AbsolutePath) -> VfsResult<()> {
+        let to = to.clone();
+        self.plugin()
+            .call_with_path(
+                from,
+                self.operation.as_ref(),
+                Retry::Never,
+                move |store, api, filesystem, from| {
+                    let to = to.clone();
+                    Box::pin(async move {
+                        let to = store.data_mut().create_absolute_path_resource(to)?;
+                        let ret = api
+                            .filesystem()
+                            .call_rename(
+                                &mut *store,
+                                filesystem,
+                                from,
+                                Resource::<AbsolutePath>::new_borrow(to.rep()),
+                            )
+                            .await;
+                        store.data_mut().take_absolute_path(to);
+                        ret
+                    })
+                },
+            )
+            .await
+    }
+
+    fn take_notices(&self) -> Vec<VfsNotice> {
+        self.plugin().take_notices()
+    }
+
+    fn with_operation(&self, operation: Arc<Operation>) -> Option<Arc<dyn AsyncVfs>> {
+        Some(Arc::new(WasmVfs {
+            mount: self.mount.clone(),
+            operation: Some(operation),
+        }))
+    }
+}
+
+/// Every call blocks the calling thread until the plugin answers, callers
+/// that must not block use [`AsyncVfs`] instead.
+impl Vfs for WasmVfs {
+    fn open(&self, path: &AbsolutePath, open_options: OpenOptions) -> VfsResult<Box<dyn File>> {
+        let file = block_on(AsyncVfs::open(self, path, open_options))?;
+        Ok(Box::new(BlockingFile::new(file)))
+    }
+
+    fn unlink(&self, path: &AbsolutePath) -> VfsResult<()> {
+        block_on(AsyncVfs::unlink(self, path))
+    }
+
+    fn stat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+        block_on(AsyncVfs::stat(self, path))
+    }
+
+    fn read_dir(&self, path: &AbsolutePath) -> VfsResult<Vec<AbsolutePath>> {
+        block_on(AsyncVfs::read_dir(self, path))
+    }
+
+    fn read_dir_with_stat(&self, path: &AbsolutePath) -> VfsResult<Vec<DirEntry>> {
+        block_on(AsyncVfs::read_dir_with_stat(self, path))
+    }
+
+    fn create_dir(&self, path: &AbsolutePath) -> VfsResult<()> {
+        block_on(AsyncVfs::create_dir(self, path))
+    }
+
+    fn create_dir_all(&self, path: &AbsolutePath) -> VfsResult<()> {
+        block_on(AsyncVfs::create_dir_all(self, path))
+    }
+
+    fn lstat(&self, path: &AbsolutePath) -> VfsResult<Stat> {
+        block_on(AsyncVfs::lstat(self, path))
+    }
+
+    fn read_link(&self, path: &AbsolutePath) -> VfsResult<String> {
+        block_on(AsyncVfs::read_link(self, path))
+    }
+
+    fn create_symlink(&self, target: &str, link: &AbsolutePath) -> VfsResult<()> {
+        block_on(AsyncVfs::create_symlink(self, target, link))
+    }
+
+    fn rename(&self, from: &AbsolutePath, to: &AbsolutePath) -> VfsResult<()> {
+        block_on(AsyncVfs::rename(self, from, to))
+    }
+
+    fn take_notices(&self) -> Vec<VfsNotice> {
+        self.plugin().take_notices()
+    }
+}
+
+/// Loads the plugin stored at `path` into the runtime shared by the whole
+/// process and mounts it on `config`, see [`PluginRuntime::load`]. Use
+/// [`WasmPlugin::mount`] to mount a plugin more than once.
+pub fn load_wasm_vfs(
+    path: &AbsolutePath,
+    policy: &PluginPolicy,
+    config: &MountConfig,
+) -> Result<WasmVfs> {
+    PluginRuntime::global()?.load(path, policy)?.mount(config)
 }
 
-pub fn load_wasm_vfs(path: &AbsolutePath, root: &AbsolutePath) -> Result<WasmVfs> {
-    let hash = sha256::digest(fs::read(path.as_str())?);
-    let mut loaded_path = env::home_dir().unwrap();
-    loaded_path.push(format!(".junkyard/plugin_vfs_{}", hash));
-
-    let mut config = Config::default();
-    config.wasm_component_model(true);
-    let engine = Engine::new(&config).unwrap();
-    let mut linker: Linker<WasmVfsState> = Linker::new(&engine);
-    let component = if !fs::exists(&loaded_path)? {
-        let component_binary = Component::from_file(&engine, path.as_str())?;
-        fs::create_dir_all(&loaded_path.parent().unwrap()).unwrap();
-        fs::write(&loaded_path, component_binary.serialize().unwrap()).unwrap();
-        component_binary
-    } else {
-        unsafe { Component::deserialize_file(&engine, &loaded_path).unwrap() }
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use vfs::{block_on, AbsolutePath, Operation, Progress, Vfs, VfsError};
+    use wasm_encoder::Instruction;
+    use wasmtime::Trap;
+
+    use crate::{
+        plugin_trap,
+        test_plugin::{
+            mem, return_slice, return_string, Body, TestPlugin, CREATE_ABSOLUTE_PATH, INFO_AREA,
+            REPORT_PROGRESS,
+        },
+        LimitExceeded, PluginLimits, PluginPolicy,
     };
-    wasmtime_wasi::add_to_linker_sync(&mut linker)?;
-    let state = WasmVfsState::new(root);
-    let mut store = Store::new(&engine, state);
-    VfsPlugin::add_to_linker(&mut linker, |s| s)?;
-    let instance = VfsPlugin::instantiate(&mut store, &component, &linker)?;
-    let vfs_plugin = instance
-        .junkyard_vfs_vfs_plugin_vfs()
-        .call_init(&mut store)?
-        .unwrap();
-    Ok(WasmVfs {
-        store: Mutex::new(store),
-        instance,
-        vfs_plugin,
-    })
+
+    /// A plugin whose `read-dir` lists the path stored at 1024.
+    fn list(path: &str) -> TestPlugin {
+        let mut body = vec![
+            Instruction::I32Const(2048),
+            Instruction::I32Const(1024),
+            Instruction::I32Const(path.len() as i32),
+            Instruction::Call(CREATE_ABSOLUTE_PATH),
+            Instruction::I32Store(mem(0, 2)),
+        ];
+        body.extend(return_slice(2048, 1));
+        TestPlugin::new()
+            .with("[method]filesystem.read-dir", Body::Code(body))
+            .with("[method]filesystem.read-link", return_string(1024, 1))
+            .with_data(1024, path)
+    }
+
+    #[test]
+    fn interrupt_is_timeout() {
+        assert_eq!(
+            plugin_trap(wasmtime::Error::new(Trap::Interrupt)),
+            VfsError::Timeout
+        );
+        assert!(matches!(
+            plugin_trap(wasmtime::Error::new(Trap::UnreachableCodeReached)),
+            VfsError::PluginTrap(_)
+        ));
+    }
+
+    #[test]
+    fn limit_exceeded() {
+        let limit = LimitExceeded::HostResources { limit: 2 };
+        assert_eq!(
+            plugin_trap(wasmtime::Error::new(limit.clone())),
+            VfsError::LimitExceeded(limit.to_string())
+        );
+    }
+
+    #[test]
+    fn read_dir_checks_children() {
+        let vfs = list("/home/user").start(&PluginPolicy::default()).unwrap();
+        assert_eq!(
+            vfs.read_dir(&AbsolutePath::new("/home")),
+            Ok(vec![AbsolutePath::new("/home/user")])
+        );
+        assert!(matches!(
+            vfs.read_dir(&AbsolutePath::new("/tmp")),
+            Err(VfsError::InvalidResponse(_))
+        ));
+    }
+
+    #[test]
+    fn refused_paths_are_released() {
+        // room for the listed folder and the returned path only
+        let policy = PluginPolicy {
+            limits: PluginLimits {
+                max_host_resources: 2,
+                ..PluginLimits::default()
+            },
+            ..PluginPolicy::default()
+        };
+        let vfs = list("/etc").start(&policy).unwrap();
+        for _ in 0..3 {
+            assert!(matches!(
+                vfs.read_dir(&AbsolutePath::new("/home")),
+                Err(VfsError::InvalidResponse(_))
+            ));
+        }
+        assert!(vfs.take_notices().is_empty());
+    }
+
+    #[test]
+    fn unknown_handle() {
+        let mut body = vec![
+            Instruction::I32Const(2048),
+            Instruction::I32Const(1234),
+            Instruction::I32Store(mem(0, 2)),
+        ];
+        body.extend(return_slice(2048, 1));
+        let vfs = list("/")
+            .with("[method]filesystem.read-dir", Body::Code(body))
+            .start(&PluginPolicy::default())
+            .unwrap();
+        assert!(matches!(
+            vfs.read_dir(&AbsolutePath::new("/")),
+            Err(VfsError::PluginTrap(_))
+        ));
+        assert_eq!(vfs.read_link(&AbsolutePath::new("/")), Ok("/".to_string()));
+    }
+
+    #[test]
+    fn read_dir_with_stat_checks_names() {
+        let mut entry = 1024_i32.to_le_bytes().to_vec();
+        entry.extend(6_i32.to_le_bytes());
+        let vfs = TestPlugin::new()
+            .with(
+                "[method]filesystem.read-dir-with-stat",
+                Body::Code(return_slice(2048, 1)),
+            )
+            .with_data(1024, "../etc")
+            .with_data(2048, entry)
+            .start(&PluginPolicy::default())
+            .unwrap();
+        assert!(matches!(
+            vfs.read_dir_with_stat(&AbsolutePath::new("/home")),
+            Err(VfsError::InvalidResponse(_))
+        ));
+    }
+
+    /// A plugin named `local` that serves `scheme`.
+    fn described(scheme: &str) -> TestPlugin {
+        let mut info = vec![0; 48];
+        info[0..4].copy_from_slice(&1024_i32.to_le_bytes());
+        info[4..8].copy_from_slice(&5_i32.to_le_bytes());
+        info[24..28].copy_from_slice(&2048_i32.to_le_bytes());
+        info[28..32].copy_from_slice(&1_i32.to_le_bytes());
+        let mut schemes = 1040_i32.to_le_bytes().to_vec();
+        schemes.extend((scheme.len() as i32).to_le_bytes());
+        TestPlugin::new()
+            .with_data(INFO_AREA, info)
+            .with_data(1024, "local")
+            .with_data(1040, scheme)
+            .with_data(2048, schemes)
+    }
+
+    #[test]
+    fn info() {
+        let vfs = described("file").start(&PluginPolicy::default()).unwrap();
+        assert_eq!(vfs.info().name, "local");
+        assert_eq!(vfs.info().schemes, vec!["file".to_string()]);
+        assert!(vfs.info().capabilities.is_empty());
+        assert!(described("not a scheme")
+            .start(&PluginPolicy::default())
+            .is_err());
+    }
+
+    #[test]
+    fn operation() {
+        // the filesystem that reports is only reachable as an `AsyncVfs`
+        use vfs::AsyncVfs;
+
+        let mut read_link = vec![
+            Instruction::I64Const(5),
+            Instruction::I64Const(10),
+            Instruction::I32Const(1024),
+            Instruction::I32Const(4),
+            Instruction::Call(REPORT_PROGRESS),
+        ];
+        read_link.extend(return_slice(1024, 4));
+        let vfs = TestPlugin::new()
+            .with("[method]filesystem.read-link", Body::Code(read_link))
+            .with("[method]filesystem.stat", Body::Trap)
+            .with_data(1024, "half")
+            .start(&PluginPolicy::default())
+            .unwrap();
+        let operation = Arc::new(Operation::new());
+        let watched = AsyncVfs::with_operation(&vfs, operation.clone()).unwrap();
+        let path = AbsolutePath::new("/link");
+
+        assert_eq!(block_on(watched.read_link(&path)), Ok("half".to_string()));
+        assert_eq!(
+            operation.progress(),
+            Progress {
+                done: 5,
+                total: 10,
+                message: "half".to_string()
+            }
+        );
+        operation.cancel();
+        // the plugin would crash if it was called
+        assert_eq!(block_on(watched.stat(&path)), Err(VfsError::Cancelled));
+        assert_eq!(Vfs::read_link(&vfs, &path), Ok("half".to_string()));
+    }
+
+    #[test]
+    fn strings_out_of_bounds() {
+        let vfs = TestPlugin::new()
+            .with("[method]filesystem.read-link", return_string(1 << 20, 16))
+            .start(&PluginPolicy::default())
+            .unwrap();
+        assert!(matches!(
+            vfs.read_link(&AbsolutePath::new("/link")),
+            Err(VfsError::PluginTrap(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let vfs = TestPlugin::new()
+            .with("[method]filesystem.read-link", return_string(1024, 2))
+            .with_data(1024, [0xc3, 0x28])
+            .start(&PluginPolicy::default())
+            .unwrap();
+        assert!(matches!(
+            vfs.read_link(&AbsolutePath::new("/link")),
+            Err(VfsError::PluginTrap(_))
+        ));
+    }
 }
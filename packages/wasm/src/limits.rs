@@ -0,0 +1,156 @@
+use std::fmt::{self, Display};
+
+use wasmtime::ResourceLimiter;
+
+use crate::PluginLimits;
+
+/// A plugin tried to use more than its [`PluginLimits`] allow.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitExceeded {
+    Memory { desired: usize, limit: usize },
+    TableElements { desired: usize, limit: usize },
+    HostResources { limit: usize },
+}
+
+impl Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::Memory { desired, limit } => write!(
+                f,
+                "the plugin needs {} bytes of memory, but only {} are allowed",
+                desired, limit
+            ),
+            LimitExceeded::TableElements { desired, limit } => write!(
+                f,
+                "the plugin needs {} table elements, but only {} are allowed",
+                desired, limit
+            ),
+            LimitExceeded::HostResources { limit } => write!(
+                f,
+                "the plugin holds more than {} paths at the same time",
+                limit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// Enforces [`PluginLimits`] on a store. Growing past a limit traps the
+/// plugin with a [`LimitExceeded`] error instead of failing inside the guest.
+pub(crate) struct PluginLimiter {
+    memory: usize,
+    table_elements: usize,
+    instances: usize,
+    host_resources: usize,
+    /// `absolute-path` resources that are currently alive
+    used_host_resources: usize,
+}
+
+impl PluginLimiter {
+    pub(crate) fn new(limits: &PluginLimits) -> PluginLimiter {
+        PluginLimiter {
+            memory: limits.max_memory_bytes as usize,
+            table_elements: limits.max_table_elements as usize,
+            instances: limits.max_instances as usize,
+            host_resources: limits.max_host_resources as usize,
+            used_host_resources: 0,
+        }
+    }
+
+    pub(crate) fn acquire_host_resource(&mut self) -> Result<(), LimitExceeded> {
+        if self.used_host_resources >= self.host_resources {
+            return Err(LimitExceeded::HostResources {
+                limit: self.host_resources,
+            });
+        }
+        self.used_host_resources += 1;
+        Ok(())
+    }
+
+    pub(crate) fn release_host_resource(&mut self) {
+        self.used_host_resources = self.used_host_resources.saturating_sub(1);
+    }
+}
+
+impl ResourceLimiter for PluginLimiter {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        if desired > self.memory {
+            return Err(LimitExceeded::Memory {
+                desired,
+                limit: self.memory,
+            }
+            .into());
+        }
+        Ok(true)
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> wasmtime::Result<bool> {
+        if desired > self.table_elements {
+            return Err(LimitExceeded::TableElements {
+                desired,
+                limit: self.table_elements,
+            }
+            .into());
+        }
+        Ok(true)
+    }
+
+    fn instances(&self) -> usize {
+        self.instances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasmtime::ResourceLimiter;
+
+    use super::{LimitExceeded, PluginLimiter};
+    use crate::PluginLimits;
+
+    fn limiter() -> PluginLimiter {
+        PluginLimiter::new(&PluginLimits {
+            max_memory_bytes: 1024,
+            max_table_elements: 10,
+            max_host_resources: 2,
+            ..PluginLimits::default()
+        })
+    }
+
+    #[test]
+    fn memory() {
+        let mut limiter = limiter();
+        assert!(limiter.memory_growing(0, 1024, None).unwrap());
+        let error = limiter.memory_growing(1024, 2048, None).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<LimitExceeded>(),
+            Some(&LimitExceeded::Memory {
+                desired: 2048,
+                limit: 1024
+            })
+        );
+    }
+
+    #[test]
+    fn host_resources() {
+        let mut limiter = limiter();
+        assert!(limiter.acquire_host_resource().is_ok());
+        assert!(limiter.acquire_host_resource().is_ok());
+        assert_eq!(
+            limiter.acquire_host_resource(),
+            Err(LimitExceeded::HostResources { limit: 2 })
+        );
+        limiter.release_host_resource();
+        assert!(limiter.acquire_host_resource().is_ok());
+    }
+}
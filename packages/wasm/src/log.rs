@@ -0,0 +1,281 @@
+use std::{
+    collections::VecDeque,
+    mem,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use wasm_vfs_api::LogLevel;
+use wasmtime_wasi::{
+    pipe::MemoryOutputPipe, HostOutputStream, StdoutStream, StreamResult, Subscribe,
+};
+
+use crate::instance::lock;
+
+/// How many records a log keeps unless configured otherwise, the oldest
+/// ones are dropped first.
+pub const DEFAULT_LOG_CAPACITY: usize = 10_000;
+
+/// Longer messages are cut, and output lines that never end are logged in
+/// pieces of this size.
+const MAX_MESSAGE_BYTES: usize = 4096;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    pub time: SystemTime,
+    /// The name of the plugin that wrote the message.
+    pub plugin: String,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// What plugins wrote, through the `log` host function or to their
+/// standard output and error.
+pub struct PluginLog {
+    records: Mutex<VecDeque<LogRecord>>,
+    capacity: usize,
+    /// records ever written, tells readers whether there is anything new
+    written: AtomicU64,
+}
+
+impl PluginLog {
+    pub fn new(capacity: usize) -> PluginLog {
+        PluginLog {
+            records: Mutex::new(VecDeque::new()),
+            capacity,
+            written: AtomicU64::new(0),
+        }
+    }
+
+    /// The records still kept, oldest first.
+    pub fn records(&self) -> Vec<LogRecord> {
+        lock(&self.records).iter().cloned().collect()
+    }
+
+    /// How many records were ever written, including the dropped ones.
+    pub fn written(&self) -> u64 {
+        self.written.load(Ordering::Relaxed)
+    }
+
+    pub fn clear(&self) {
+        lock(&self.records).clear();
+    }
+
+    fn push(&self, plugin: &str, level: LogLevel, message: &str) {
+        let mut end = message.len().min(MAX_MESSAGE_BYTES);
+        while !message.is_char_boundary(end) {
+            end -= 1;
+        }
+        let mut records = lock(&self.records);
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(LogRecord {
+            time: SystemTime::now(),
+            plugin: plugin.to_string(),
+            level,
+            message: message[..end].to_string(),
+        });
+        self.written.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Default for PluginLog {
+    fn default() -> Self {
+        PluginLog::new(DEFAULT_LOG_CAPACITY)
+    }
+}
+
+/// Writes to a [`PluginLog`] on behalf of one plugin.
+#[derive(Clone)]
+pub(crate) struct PluginLogger {
+    plugin: Arc<str>,
+    log: Arc<PluginLog>,
+}
+
+impl PluginLogger {
+    pub(crate) fn new(plugin: &str, log: Arc<PluginLog>) -> PluginLogger {
+        PluginLogger {
+            plugin: plugin.into(),
+            log,
+        }
+    }
+
+    pub(crate) fn plugin(&self) -> &str {
+        &self.plugin
+    }
+
+    pub(crate) fn log(&self, level: LogLevel, message: &str) {
+        self.log.push(&self.plugin, level, message);
+    }
+}
+
+/// A plugin's standard output or error, written to the log a line at a
+/// time, and kept in `capture` as well when its policy captures stdio.
+pub(crate) struct LogOutput {
+    pub(crate) logger: PluginLogger,
+    pub(crate) level: LogLevel,
+    pub(crate) capture: Option<MemoryOutputPipe>,
+}
+
+impl StdoutStream for LogOutput {
+    fn stream(&self) -> Box<dyn HostOutputStream> {
+        Box::new(LogStream {
+            logger: self.logger.clone(),
+            level: self.level,
+            capture: self.capture.clone(),
+            line: vec![],
+        })
+    }
+
+    fn isatty(&self) -> bool {
+        false
+    }
+}
+
+/// An output stream of one instance, so that instances running at the
+/// same time do not mix up their lines.
+struct LogStream {
+    logger: PluginLogger,
+    level: LogLevel,
+    capture: Option<MemoryOutputPipe>,
+    /// the end of the output, that is not a whole line yet
+    line: Vec<u8>,
+}
+
+impl LogStream {
+    fn log_line(&mut self, end: usize) {
+        let rest = self.line.split_off(end);
+        let line = mem::replace(&mut self.line, rest);
+        let line = String::from_utf8_lossy(&line);
+        self.logger
+            .log(self.level, line.trim_end_matches(['\n', '\r']));
+    }
+}
+
+impl HostOutputStream for LogStream {
+    fn write(&mut self, bytes: Bytes) -> StreamResult<()> {
+        if let Some(capture) = &mut self.capture {
+            capture.write(bytes.clone())?;
+        }
+        self.line.extend_from_slice(&bytes);
+        while let Some(newline) = self.line.iter().position(|byte| *byte == b'\n') {
+            self.log_line(newline + 1);
+        }
+        while self.line.len() >= MAX_MESSAGE_BYTES {
+            self.log_line(MAX_MESSAGE_BYTES);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> StreamResult<()> {
+        // lines are logged once they end, a flush does not end them
+        Ok(())
+    }
+
+    fn check_write(&mut self) -> StreamResult<usize> {
+        match &mut self.capture {
+            Some(capture) => capture.check_write(),
+            None => Ok(MAX_MESSAGE_BYTES),
+        }
+    }
+}
+
+#[async_trait]
+impl Subscribe for LogStream {
+    async fn ready(&mut self) {}
+}
+
+impl Drop for LogStream {
+    fn drop(&mut self) {
+        if !self.line.is_empty() {
+            self.log_line(self.line.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use wasm_encoder::Instruction;
+    use wasm_vfs_api::LogLevel;
+    use wasmtime_wasi::StdoutStream;
+
+    use super::{LogOutput, PluginLog, PluginLogger, MAX_MESSAGE_BYTES};
+    use crate::{
+        test_plugin::{return_filesystem, Body, TestPlugin, LOG},
+        PluginPolicy, PluginRuntime,
+    };
+
+    fn messages(log: &PluginLog) -> Vec<String> {
+        log.records()
+            .into_iter()
+            .map(|record| record.message)
+            .collect()
+    }
+
+    #[test]
+    fn bounded() {
+        let log = Arc::new(PluginLog::new(2));
+        let logger = PluginLogger::new("test", log.clone());
+        for message in ["first", "second", "third"] {
+            logger.log(LogLevel::Info, message);
+        }
+        logger.log(LogLevel::Warn, &"é".repeat(MAX_MESSAGE_BYTES));
+
+        assert_eq!(log.written(), 4);
+        let records = log.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].message, "third");
+        assert_eq!(records[0].plugin, "test");
+        assert_eq!(records[1].level, LogLevel::Warn);
+        assert_eq!(records[1].message, "é".repeat(MAX_MESSAGE_BYTES / 2));
+    }
+
+    #[test]
+    fn output_lines() {
+        let log = Arc::new(PluginLog::default());
+        let output = LogOutput {
+            logger: PluginLogger::new("test", log.clone()),
+            level: LogLevel::Info,
+            capture: None,
+        };
+        let mut stream = output.stream();
+        stream.write(Bytes::from("one\r\ntw")).unwrap();
+        stream.write(Bytes::from("o\n\nthr")).unwrap();
+        assert_eq!(messages(&log), vec!["one", "two", ""]);
+        stream.write(Bytes::from("ee")).unwrap();
+        drop(stream);
+        assert_eq!(messages(&log), vec!["one", "two", "", "three"]);
+    }
+
+    #[test]
+    fn host_function() {
+        let mut init = vec![
+            Instruction::I32Const(LogLevel::Warn as i32),
+            Instruction::I32Const(1024),
+            Instruction::I32Const(7),
+            Instruction::Call(LOG),
+        ];
+        init.extend(return_filesystem());
+        let runtime = PluginRuntime::new().unwrap();
+        TestPlugin::new()
+            .with("init", Body::Code(init))
+            .with_data(1024, "mounted")
+            .start_in(&runtime, &PluginPolicy::default())
+            .unwrap();
+
+        let records = runtime.log().records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].plugin, "test");
+        assert_eq!(records[0].level, LogLevel::Warn);
+        assert_eq!(records[0].message, "mounted");
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    sync::{Arc, Mutex, RwLock, Weak},
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use vfs::block_on;
+use wasm_vfs_api::{MountConfig, PluginInfo, VfsExportsPre};
+
+use crate::{
+    epoch::{self, EpochTicker},
+    instance::{lock, PluginPool},
+    response,
+    secret::MountSecrets,
+    PluginPolicy, PluginServices, WasmVfs, WasmVfsState,
+};
+
+/// A plugin that is compiled and linked, ready to be mounted.
+///
+/// Every mount is a filesystem of its own, served by instances of its own,
+/// so mounts never share memory and a crash in one of them leaves the
+/// others running.
+pub struct WasmPlugin {
+    pre: VfsExportsPre<WasmVfsState>,
+    policy: PluginPolicy,
+    services: PluginServices,
+    /// epoch ticks a single call may take
+    deadline: u64,
+    ticker: Arc<EpochTicker>,
+    /// the mounts served by this plugin, until they are dropped or moved to
+    /// a newer version of it
+    mounts: Mutex<Vec<Weak<Mount>>>,
+}
+
+/// What a mount runs, it is replaced as a whole when the plugin is
+/// reloaded.
+pub(crate) struct Mounted {
+    pub(crate) plugin: Arc<PluginPool>,
+    pub(crate) info: PluginInfo,
+}
+
+/// A mount of a plugin, shared by the [`WasmVfs`] handles to it.
+pub(crate) struct Mount {
+    config: MountConfig,
+    /// what the user asked to remember for this mount, whatever version of
+    /// the plugin serves it
+    secrets: Arc<MountSecrets>,
+    mounted: RwLock<Arc<Mounted>>,
+}
+
+/// Mounts of a plugin started on a newer version of it, see
+/// [`WasmPlugin::start_mounts`].
+pub struct StartedMounts {
+    mounts: Vec<(Arc<Mount>, Mounted)>,
+}
+
+impl Mount {
+    pub(crate) fn current(&self) -> Arc<Mounted> {
+        self.mounted
+            .read()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()
+    }
+}
+
+impl WasmPlugin {
+    pub(crate) fn new(
+        pre: VfsExportsPre<WasmVfsState>,
+        policy: &PluginPolicy,
+        services: PluginServices,
+        ticker: Arc<EpochTicker>,
+    ) -> WasmPlugin {
+        WasmPlugin {
+            pre,
+            services,
+            deadline: epoch::deadline_ticks(Duration::from_millis(policy.limits.call_timeout_ms)),
+            policy: policy.clone(),
+            ticker,
+            mounts: Mutex::new(vec![]),
+        }
+    }
+
+    /// Mounts a filesystem of the plugin on `config.uri`, which fails if the
+    /// plugin does not accept the configuration.
+    pub fn mount(&self, config: &MountConfig) -> Result<WasmVfs> {
+        let secrets = Arc::new(MountSecrets::new(
+            self.services.prompt.clone(),
+            self.services.logger.plugin(),
+            &config.uri,
+        ));
+        let mounted = block_on(self.start(config, &secrets))?;
+        let mount = Arc::new(Mount {
+            config: config.clone(),
+            secrets,
+            mounted: RwLock::new(Arc::new(mounted)),
+        });
+        lock(&self.mounts).push(Arc::downgrade(&mount));
+        Ok(WasmVfs {
+            mount,
+            operation: None,
+        })
+    }
+
+    /// Moves the mounts of `previous`, an older version of the plugin, to
+    /// this one, see [`WasmPlugin::start_mounts`] and
+    /// [`WasmPlugin::move_mounts`].
+    pub fn take_mounts(&self, previous: &WasmPlugin) -> Result<()> {
+        let started = self.start_mounts(previous)?;
+        self.move_mounts(previous, started);
+        Ok(())
+    }
+
+    /// Starts the mounts of `previous`, an older version of the plugin, on
+    /// this one, they keep using `previous` until they are moved. If one of
+    /// the mounts fails to start, none of them is started.
+    pub fn start_mounts(&self, previous: &WasmPlugin) -> Result<StartedMounts> {
+        let mounts = lock(&previous.mounts)
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>();
+        let mounts = block_on(async {
+            let mut started = vec![];
+            for mount in mounts {
+                let mounted = self.start(&mount.config, &mount.secrets).await?;
+                started.push((mount, mounted));
+            }
+            Ok::<_, anyhow::Error>(started)
+        })?;
+        Ok(StartedMounts { mounts })
+    }
+
+    /// Calls made from now on go to this plugin, files opened before stay
+    /// with the previous version until they are closed.
+    pub fn move_mounts(&self, previous: &WasmPlugin, started: StartedMounts) {
+        let moved = started
+            .mounts
+            .iter()
+            .map(|(mount, _)| mount.clone())
+            .collect::<Vec<_>>();
+        for (mount, mounted) in started.mounts {
+            *mount
+                .mounted
+                .write()
+                .unwrap_or_else(|error| error.into_inner()) = Arc::new(mounted);
+        }
+        // mounts made since they were started stay on the previous version
+        lock(&previous.mounts).retain(|mount| {
+            mount
+                .upgrade()
+                .is_some_and(|mount| !moved.iter().any(|moved| Arc::ptr_eq(moved, &mount)))
+        });
+        lock(&self.mounts).extend(moved.iter().map(Arc::downgrade));
+    }
+
+    async fn start(&self, config: &MountConfig, secrets: &Arc<MountSecrets>) -> Result<Mounted> {
+        let plugin = PluginPool::start(
+            self.pre.clone(),
+            self.policy.clone(),
+            config.clone(),
+            self.services.clone(),
+            secrets.clone(),
+            self.deadline,
+            self.ticker.clone(),
+        )
+        .await?;
+        let info = plugin
+            .info()
+            .await
+            .and_then(|info| response::check_info(&info).map(|_| info))
+            .map_err(|error| {
+                anyhow!("the plugin failed to describe itself: {}", error.message())
+            })?;
+        Ok(Mounted {
+            plugin: Arc::new(plugin),
+            info,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use vfs::{block_on, AbsolutePath, Operation, Vfs};
+    use wasm_encoder::Instruction;
+
+    use crate::{
+        test_plugin::{mem, return_filesystem, return_string, Body, TestPlugin, RET_AREA},
+        MountConfig, PluginPolicy, PluginRuntime, WasmPlugin,
+    };
+
+    /// Where `init` keeps the uri it was given.
+    const URI: i32 = 512;
+
+    #[test]
+    fn each_mount_gets_its_config() {
+        let mut init = vec![
+            Instruction::I32Const(URI),
+            Instruction::LocalGet(0),
+            Instruction::I32Store(mem(0, 2)),
+            Instruction::I32Const(URI),
+            Instruction::LocalGet(1),
+            Instruction::I32Store(mem(4, 2)),
+        ];
+        init.extend(return_filesystem());
+        // read-link answers with the uri
+        let read_link = vec![
+            Instruction::I32Const(RET_AREA),
+            Instruction::I32Const(0),
+            Instruction::I32Store8(mem(0, 0)),
+            Instruction::I32Const(RET_AREA),
+            Instruction::I32Const(URI),
+            Instruction::I64Load(mem(0, 3)),
+            Instruction::I64Store(mem(4, 2)),
+            Instruction::I32Const(RET_AREA),
+        ];
+        let plugin = TestPlugin::new()
+            .with("init", Body::Code(init))
+            .with("[method]filesystem.read-link", Body::Code(read_link))
+            .prepare_in(&PluginRuntime::new().unwrap(), &PluginPolicy::default())
+            .unwrap();
+
+        let first = plugin.mount(&MountConfig::new("zip:///a.zip")).unwrap();
+        let second = plugin.mount(&MountConfig::new("zip:///b.zip")).unwrap();
+        let path = AbsolutePath::new("/link");
+        assert_eq!(first.read_link(&path), Ok("zip:///a.zip".to_string()));
+        assert_eq!(second.read_link(&path), Ok("zip:///b.zip".to_string()));
+    }
+
+    #[test]
+    fn refused_mounts() {
+        // err(not-found)
+        let init = vec![
+            Instruction::I32Const(RET_AREA),
+            Instruction::I32Const(1),
+            Instruction::I32Store8(mem(0, 0)),
+            Instruction::I32Const(RET_AREA),
+            Instruction::I32Const(0),
+            Instruction::I32Store8(mem(4, 0)),
+            Instruction::I32Const(RET_AREA),
+        ];
+        let error = TestPlugin::new()
+            .with("init", Body::Code(init))
+            .start(&PluginPolicy::default())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("failed to mount"));
+    }
+
+    #[test]
+    fn reload() {
+        let runtime = PluginRuntime::new().unwrap();
+        let version = |target: &str| -> WasmPlugin {
+            TestPlugin::new()
+                .with(
+                    "[method]filesystem.read-link",
+                    return_string(1024, target.len() as i32),
+                )
+                .with_data(1024, target)
+                .prepare_in(&runtime, &PluginPolicy::default())
+                .unwrap()
+        };
+        let first = version("first");
+        let vfs = first.mount(&MountConfig::new("test:///")).unwrap();
+        let watched = vfs::AsyncVfs::with_operation(&vfs, Arc::new(Operation::new())).unwrap();
+        let path = AbsolutePath::new("/link");
+
+        let second = version("second");
+        // the versions keep their values in the same place
+        assert!(Arc::ptr_eq(
+            &first.services.storage,
+            &second.services.storage
+        ));
+        second.take_mounts(&first).unwrap();
+        assert_eq!(vfs.read_link(&path), Ok("second".to_string()));
+        // what the user asked to remember is not asked again
+        assert!(Arc::ptr_eq(&vfs.plugin().secrets, &vfs.mount.secrets));
+        assert_eq!(
+            block_on(vfs::AsyncVfs::read_link(&*watched, &path)),
+            Ok("second".to_string())
+        );
+
+        // a version that cannot mount leaves the mount where it was
+        let broken = TestPlugin::new()
+            .with("init", Body::Trap)
+            .prepare_in(&runtime, &PluginPolicy::default())
+            .unwrap();
+        assert!(broken.take_mounts(&second).is_err());
+        assert_eq!(vfs.read_link(&path), Ok("second".to_string()));
+        let third = version("third");
+        third.take_mounts(&second).unwrap();
+        assert_eq!(vfs.read_link(&path), Ok("third".to_string()));
+    }
+}
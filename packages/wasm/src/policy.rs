@@ -0,0 +1,302 @@
+use std::{collections::BTreeMap, env, fs, path::PathBuf, time::Duration};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use wasmtime_wasi::{
+    pipe::MemoryOutputPipe, Deterministic, DirPerms, FilePerms, HostMonotonicClock, HostWallClock,
+    WasiCtx, WasiCtxBuilder,
+};
+
+use crate::{
+    log::{LogOutput, PluginLogger},
+    LogLevel, Permission,
+};
+
+/// How much of the plugin's output is kept when stdio is captured, writing
+/// past this limit traps the plugin.
+const CAPTURE_CAPACITY: usize = 1024 * 1024;
+
+/// Everything a plugin is allowed to access. The default policy grants
+/// nothing: no folders, no environment, no output and frozen clocks.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PluginPolicy {
+    pub preopens: Vec<Preopen>,
+    pub env: BTreeMap<String, String>,
+    pub stdio: Stdio,
+    pub clocks: bool,
+    pub random: bool,
+    pub limits: PluginLimits,
+}
+
+/// Bounds on the resources a plugin may use while serving calls.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PluginLimits {
+    /// How long a single call into the plugin may run before it is
+    /// interrupted and fails with [`vfs::VfsError::Timeout`].
+    pub call_timeout_ms: u64,
+    /// The largest size a single linear memory may grow to.
+    pub max_memory_bytes: u64,
+    pub max_table_elements: u32,
+    /// How many core instances a component may create.
+    pub max_instances: u32,
+    /// How many paths the plugin may hold at the same time.
+    pub max_host_resources: u32,
+    /// How many times in a row a crashed plugin is started again before
+    /// it is disabled.
+    pub max_restarts: u32,
+    /// How many calls may run in the plugin at the same time, each one in
+    /// an instance of its own. Plugins that keep state between calls need 1.
+    pub concurrent_calls: u32,
+    /// How many bytes the keys and values the plugin stores between
+    /// sessions may take.
+    pub max_storage_bytes: u64,
+}
+
+impl Default for PluginLimits {
+    fn default() -> Self {
+        PluginLimits {
+            call_timeout_ms: 5000,
+            max_memory_bytes: 256 * 1024 * 1024,
+            max_table_elements: 100_000,
+            max_instances: 64,
+            max_host_resources: 262_144,
+            max_restarts: 3,
+            concurrent_calls: 4,
+            max_storage_bytes: 1024 * 1024,
+        }
+    }
+}
+
+/// A host folder made visible to the plugin at `guest`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preopen {
+    pub host: String,
+    pub guest: String,
+    #[serde(default)]
+    pub write: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stdio {
+    /// The plugin's output is discarded.
+    #[default]
+    Null,
+    /// Every line of the plugin's output is logged, see
+    /// [`crate::PluginRuntime::log`]. Lines written to standard error are
+    /// warnings.
+    Log,
+    /// The plugin's output is logged and kept in memory as well, see
+    /// [`crate::WasmVfs::stdout`].
+    Capture,
+    /// The plugin shares the terminal with junkyard.
+    Inherit,
+}
+
+impl PluginPolicy {
+    pub fn from_toml(source: &str) -> Result<PluginPolicy> {
+        Ok(toml::from_str(source)?)
+    }
+
+    /// Loads the policy that the user granted to the plugin named `name` in
+    /// its manifest, from `~/.junkyard/policies/<plugin name>.toml`. Plugins
+    /// without a policy file, or without a home folder to keep it in, get
+    /// the default one.
+    pub fn for_plugin(name: &str) -> Result<PluginPolicy> {
+        let Some(path) = policy_path(name) else {
+            return Ok(PluginPolicy::default());
+        };
+        if !fs::exists(&path)? {
+            return Ok(PluginPolicy::default());
+        }
+        let source = fs::read_to_string(&path)?;
+        PluginPolicy::from_toml(&source)
+            .with_context(|| format!("invalid plugin policy {}", path.display()))
+    }
+
+    pub fn grants(&self, permission: Permission) -> bool {
+        match permission {
+            Permission::Folders => !self.preopens.is_empty(),
+            Permission::Write => self.preopens.iter().any(|preopen| preopen.write),
+            Permission::Env => !self.env.is_empty(),
+            Permission::Stdio => self.stdio != Stdio::Null,
+            Permission::Clocks => self.clocks,
+            Permission::Random => self.random,
+        }
+    }
+
+    /// The buffers that keep the plugin's output, shared by all its
+    /// instances, if the policy captures stdio.
+    pub(crate) fn captured_stdio(&self) -> Option<CapturedStdio> {
+        match self.stdio {
+            Stdio::Capture => Some(CapturedStdio {
+                stdout: MemoryOutputPipe::new(CAPTURE_CAPACITY),
+                stderr: MemoryOutputPipe::new(CAPTURE_CAPACITY),
+            }),
+            Stdio::Null | Stdio::Log | Stdio::Inherit => None,
+        }
+    }
+
+    pub(crate) fn build(
+        &self,
+        captured: Option<&CapturedStdio>,
+        logger: &PluginLogger,
+    ) -> Result<WasiCtx> {
+        let mut builder = WasiCtxBuilder::new();
+        for preopen in &self.preopens {
+            let (dir_perms, file_perms) = if preopen.write {
+                (DirPerms::all(), FilePerms::all())
+            } else {
+                (DirPerms::READ, FilePerms::READ)
+            };
+            builder
+                .preopened_dir(&preopen.host, &preopen.guest, dir_perms, file_perms)
+                .with_context(|| format!("unable to preopen {}", preopen.host))?;
+        }
+        for (key, value) in &self.env {
+            builder.env(key, value);
+        }
+        match self.stdio {
+            Stdio::Log | Stdio::Capture => {
+                builder.stdout(LogOutput {
+                    logger: logger.clone(),
+                    level: LogLevel::Info,
+                    capture: captured.map(|captured| captured.stdout.clone()),
+                });
+                builder.stderr(LogOutput {
+                    logger: logger.clone(),
+                    level: LogLevel::Warn,
+                    capture: captured.map(|captured| captured.stderr.clone()),
+                });
+            }
+            Stdio::Inherit => {
+                builder.inherit_stdio();
+            }
+            Stdio::Null => {}
+        }
+        if !self.clocks {
+            builder.wall_clock(FrozenClock);
+            builder.monotonic_clock(FrozenClock);
+        }
+        if !self.random {
+            builder.secure_random(Deterministic::new(vec![0]));
+            builder.insecure_random(Deterministic::new(vec![0]));
+            builder.insecure_random_seed(0);
+        }
+        Ok(builder.build())
+    }
+}
+
+fn policy_path(name: &str) -> Option<PathBuf> {
+    let home = env::home_dir()?;
+    Some(
+        home.join(".junkyard/policies")
+            .join(format!("{}.toml", name)),
+    )
+}
+
+#[derive(Clone)]
+pub(crate) struct CapturedStdio {
+    pub(crate) stdout: MemoryOutputPipe,
+    pub(crate) stderr: MemoryOutputPipe,
+}
+
+/// A clock that always reads zero, given to plugins that are not allowed
+/// to know the time.
+struct FrozenClock;
+
+impl HostWallClock for FrozenClock {
+    fn resolution(&self) -> Duration {
+        Duration::from_secs(1)
+    }
+
+    fn now(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+impl HostMonotonicClock for FrozenClock {
+    fn resolution(&self) -> u64 {
+        1_000_000_000
+    }
+
+    fn now(&self) -> u64 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PluginLimits, PluginPolicy, Preopen, Stdio};
+
+    #[test]
+    fn default_grants_nothing() {
+        let policy = PluginPolicy::from_toml("").unwrap();
+        assert_eq!(policy, PluginPolicy::default());
+        assert!(policy.preopens.is_empty());
+        assert_eq!(policy.stdio, Stdio::Null);
+        assert!(!policy.clocks);
+        assert!(!policy.random);
+        assert_eq!(policy.limits.call_timeout_ms, 5000);
+    }
+
+    #[test]
+    fn from_toml() {
+        let policy = PluginPolicy::from_toml(
+            r#"
+            stdio = "capture"
+            clocks = true
+
+            [[preopens]]
+            host = "/home/user"
+            guest = "/"
+
+            [[preopens]]
+            host = "/tmp"
+            guest = "/tmp"
+            write = true
+
+            [env]
+            LANG = "C"
+
+            [limits]
+            call_timeout_ms = 250
+            max_memory_bytes = 1048576
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            policy.preopens,
+            vec![
+                Preopen {
+                    host: "/home/user".to_string(),
+                    guest: "/".to_string(),
+                    write: false
+                },
+                Preopen {
+                    host: "/tmp".to_string(),
+                    guest: "/tmp".to_string(),
+                    write: true
+                },
+            ]
+        );
+        assert_eq!(policy.env.get("LANG").map(String::as_str), Some("C"));
+        assert_eq!(policy.stdio, Stdio::Capture);
+        assert!(policy.clocks);
+        assert!(!policy.random);
+        assert_eq!(policy.limits.call_timeout_ms, 250);
+        assert_eq!(policy.limits.max_memory_bytes, 1048576);
+        assert_eq!(
+            policy.limits.max_host_resources,
+            PluginLimits::default().max_host_resources
+        );
+    }
+
+    #[test]
+    fn unknown_fields() {
+        assert!(PluginPolicy::from_toml("network = true").is_err());
+    }
+}
@@ -0,0 +1,500 @@
+use std::{
+    collections::HashSet,
+    env,
+    fmt::{self, Display},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use vfs::AbsolutePath;
+
+use crate::{
+    instance::lock, response::is_scheme, ComponentCache, PluginPolicy, PluginRuntime, WasmPlugin,
+};
+
+/// Plugins installed for every user of the machine.
+pub const SYSTEM_PLUGINS_DIR: &str = "/usr/share/junkyard/plugins";
+
+/// What a plugin says about itself, in a `<plugin name>.toml` file next to
+/// its `<plugin name>.wasm` component.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginManifest {
+    pub name: String,
+    pub version: String,
+    /// The URI scheme of the paths the plugin serves, like `file` or `s3`.
+    pub scheme: String,
+    /// The folder panels open the plugin in.
+    #[serde(default = "default_root")]
+    pub root: String,
+    /// What the plugin cannot work without, its policy has to grant all of
+    /// it before the plugin is loaded.
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
+}
+
+fn default_root() -> String {
+    "/".to_string()
+}
+
+/// Something a plugin may be granted by its [`PluginPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Permission {
+    /// At least one host folder.
+    Folders,
+    /// A host folder it may write to.
+    Write,
+    Env,
+    Stdio,
+    Clocks,
+    Random,
+}
+
+impl Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Permission::Folders => "folders",
+            Permission::Write => "write",
+            Permission::Env => "env",
+            Permission::Stdio => "stdio",
+            Permission::Clocks => "clocks",
+            Permission::Random => "random",
+        })
+    }
+}
+
+impl PluginManifest {
+    pub fn from_toml(source: &str) -> Result<PluginManifest> {
+        let manifest: PluginManifest = toml::from_str(source)?;
+        if manifest.name.is_empty() {
+            bail!("the plugin has no name");
+        }
+        // the policy and the storage of the plugin are files named after it
+        if manifest.name.starts_with('.') || manifest.name.contains(['/', '\\']) {
+            bail!("{:?} cannot be a plugin name", manifest.name);
+        }
+        if !is_scheme(&manifest.scheme) {
+            bail!("{:?} is not a URI scheme", manifest.scheme);
+        }
+        if !manifest.root.starts_with('/') {
+            bail!("the root {:?} is not an absolute path", manifest.root);
+        }
+        Ok(manifest)
+    }
+}
+
+/// A plugin found on disk, it is loaded the first time it is used.
+pub struct RegisteredPlugin {
+    pub manifest: PluginManifest,
+    pub path: AbsolutePath,
+    loaded: Mutex<Option<Loaded>>,
+}
+
+struct Loaded {
+    plugin: Arc<WasmPlugin>,
+    /// the component file the plugin was loaded from
+    stamp: Option<Stamp>,
+}
+
+/// Tells whether a file changed, without reading it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Stamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl Stamp {
+    fn of(path: &AbsolutePath) -> Option<Stamp> {
+        let metadata = fs::metadata(path.as_str()).ok()?;
+        Some(Stamp {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+impl RegisteredPlugin {
+    /// The folder panels open the plugin in.
+    pub fn root(&self) -> AbsolutePath {
+        AbsolutePath::new(&self.manifest.root)
+    }
+
+    /// What the plugin is mounted on unless the user picks something else.
+    pub fn default_uri(&self) -> String {
+        format!("{}:///", self.manifest.scheme)
+    }
+
+    /// Loads the plugin with the policy the user granted it, or returns
+    /// the already loaded one. A loaded plugin can be mounted many times.
+    pub fn load(&self) -> Result<Arc<WasmPlugin>> {
+        let mut loaded = lock(&self.loaded);
+        if let Some(loaded) = loaded.as_ref() {
+            return Ok(loaded.plugin.clone());
+        }
+        // taken first, a change made while the plugin loads is not missed
+        let stamp = Stamp::of(&self.path);
+        let plugin = Arc::new(self.load_from_disk()?);
+        *loaded = Some(Loaded {
+            plugin: plugin.clone(),
+            stamp,
+        });
+        Ok(plugin)
+    }
+
+    /// Loads the plugin again if its component file changed since it was
+    /// loaded, its mounts move to the new version. The previous version
+    /// keeps serving them if the new one fails to compile or to mount, until
+    /// the file changes again. Returns whether the plugin was reloaded.
+    pub fn reload_if_changed(&self) -> Result<bool> {
+        let Some((previous, previous_stamp)) = lock(&self.loaded)
+            .as_ref()
+            .map(|loaded| (loaded.plugin.clone(), loaded.stamp))
+        else {
+            return Ok(false);
+        };
+        let stamp = Stamp::of(&self.path);
+        // a file that is being replaced may be missing for a moment
+        if stamp.is_none() || stamp == previous_stamp {
+            return Ok(false);
+        }
+        // nothing is locked while the new version compiles and mounts, which
+        // may wait for the user to type a password
+        let started = self.load_from_disk().and_then(|plugin| {
+            let mounts = plugin.start_mounts(&previous)?;
+            Ok((plugin, mounts))
+        });
+        let plugin = {
+            let mut loaded = lock(&self.loaded);
+            let Some(loaded) = loaded
+                .as_mut()
+                .filter(|loaded| loaded.stamp == previous_stamp)
+            else {
+                // another reload got there first
+                return Ok(false);
+            };
+            loaded.stamp = stamp;
+            let (plugin, mounts) = started?;
+            plugin.move_mounts(&previous, mounts);
+            let plugin = Arc::new(plugin);
+            loaded.plugin = plugin.clone();
+            plugin
+        };
+        // mounts made on the previous version while the new one started
+        plugin.take_mounts(&previous)?;
+        Ok(true)
+    }
+
+    fn load_from_disk(&self) -> Result<WasmPlugin> {
+        let policy = PluginPolicy::for_plugin(&self.manifest.name)?;
+        let missing = self.missing_permissions(&policy);
+        if !missing.is_empty() {
+            bail!(
+                "the plugin {} needs {} permissions that its policy does not grant",
+                self.manifest.name,
+                missing
+                    .iter()
+                    .map(Permission::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+        // named like its policy, renaming the file keeps what it stored
+        PluginRuntime::global()?.load_as(
+            &self.manifest.name,
+            &self.path,
+            &policy,
+            ComponentCache::in_home().as_ref(),
+        )
+    }
+
+    fn missing_permissions(&self, policy: &PluginPolicy) -> Vec<Permission> {
+        self.manifest
+            .permissions
+            .iter()
+            .copied()
+            .filter(|permission| !policy.grants(*permission))
+            .collect()
+    }
+}
+
+/// The plugins installed in the plugin folders.
+#[derive(Default)]
+pub struct PluginRegistry {
+    plugins: Vec<Arc<RegisteredPlugin>>,
+    /// plugins that were skipped, and why
+    errors: Vec<String>,
+}
+
+impl PluginRegistry {
+    /// Scans `~/.junkyard/plugins` and [`SYSTEM_PLUGINS_DIR`], plugins
+    /// installed by the user replace system ones with the same name.
+    pub fn discover() -> PluginRegistry {
+        let mut dirs = vec![];
+        if let Some(mut home) = env::home_dir() {
+            home.push(".junkyard/plugins");
+            dirs.push(home);
+        }
+        dirs.push(PathBuf::from(SYSTEM_PLUGINS_DIR));
+        PluginRegistry::scan(&dirs)
+    }
+
+    /// Scans `dirs` in order, the first plugin found with a name wins.
+    /// Folders that do not exist are ignored.
+    pub fn scan(dirs: &[PathBuf]) -> PluginRegistry {
+        let mut registry = PluginRegistry::default();
+        let mut names = HashSet::new();
+        for dir in dirs {
+            let components = match components_in(dir) {
+                Ok(components) => components,
+                Err(error) => {
+                    registry
+                        .errors
+                        .push(format!("unable to read {}: {}", dir.display(), error));
+                    continue;
+                }
+            };
+            for component in components {
+                match read_manifest(&component) {
+                    Ok(manifest) => {
+                        if names.insert(manifest.name.clone()) {
+                            registry.plugins.push(Arc::new(RegisteredPlugin {
+                                manifest,
+                                path: AbsolutePath::new(component.to_string_lossy()),
+                                loaded: Mutex::new(None),
+                            }));
+                        }
+                    }
+                    Err(error) => registry.errors.push(format!("{:#}", error)),
+                }
+            }
+        }
+        registry
+    }
+
+    pub fn plugins(&self) -> &[Arc<RegisteredPlugin>] {
+        &self.plugins
+    }
+
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    pub fn find(&self, name: &str) -> Option<Arc<RegisteredPlugin>> {
+        self.plugins
+            .iter()
+            .find(|plugin| plugin.manifest.name == name)
+            .cloned()
+    }
+
+    /// Reloads the loaded plugins whose component file changed, see
+    /// [`RegisteredPlugin::reload_if_changed`]. Returns the name of every
+    /// plugin that changed, and whether it was reloaded.
+    pub fn reload_changed(&self) -> Vec<(String, Result<()>)> {
+        self.plugins
+            .iter()
+            .filter_map(|plugin| match plugin.reload_if_changed() {
+                Ok(false) => None,
+                reloaded => Some((plugin.manifest.name.clone(), reloaded.map(|_| ()))),
+            })
+            .collect()
+    }
+
+    /// The first plugin that serves `scheme`.
+    pub fn for_scheme(&self, scheme: &str) -> Option<Arc<RegisteredPlugin>> {
+        self.plugins
+            .iter()
+            .find(|plugin| plugin.manifest.scheme == scheme)
+            .cloned()
+    }
+}
+
+/// The `.wasm` files in `dir`, sorted so that scans are repeatable.
+fn components_in(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(error) => return Err(error),
+    };
+    let mut components = vec![];
+    for entry in read_dir {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "wasm")
+        {
+            components.push(path);
+        }
+    }
+    components.sort();
+    Ok(components)
+}
+
+fn read_manifest(component: &Path) -> Result<PluginManifest> {
+    let path = component.with_extension("toml");
+    let source = fs::read_to_string(&path)
+        .with_context(|| format!("the plugin {} has no manifest", component.display()))?;
+    PluginManifest::from_toml(&source)
+        .with_context(|| format!("invalid plugin manifest {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs, path::PathBuf, slice};
+
+    use vfs::{AbsolutePath, Vfs};
+
+    use super::{Permission, PluginManifest, PluginRegistry};
+    use crate::{
+        test_plugin::{return_string, TestPlugin},
+        MountConfig, PluginPolicy, Preopen,
+    };
+
+    const MANIFEST: &str = r#"
+        name = "local"
+        version = "0.1.0"
+        scheme = "file"
+        permissions = ["folders", "clocks"]
+    "#;
+
+    fn plugins_dir(name: &str, plugins: &[(&str, &str)]) -> PathBuf {
+        let dir = temp_dir().join(format!("junkyard_plugins_{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (stem, manifest) in plugins {
+            fs::write(dir.join(format!("{}.wasm", stem)), "").unwrap();
+            if !manifest.is_empty() {
+                fs::write(dir.join(format!("{}.toml", stem)), manifest).unwrap();
+            }
+        }
+        dir
+    }
+
+    #[test]
+    fn manifest() {
+        let manifest = PluginManifest::from_toml(MANIFEST).unwrap();
+        assert_eq!(manifest.name, "local");
+        assert_eq!(manifest.scheme, "file");
+        assert_eq!(manifest.root, "/");
+        assert_eq!(
+            manifest.permissions,
+            vec![Permission::Folders, Permission::Clocks]
+        );
+        for invalid in [
+            "name = \"\"\nversion = \"1\"\nscheme = \"file\"",
+            "name = \"../local\"\nversion = \"1\"\nscheme = \"file\"",
+            "name = \"a\"\nversion = \"1\"\nscheme = \"3d\"",
+            "name = \"a\"\nversion = \"1\"\nscheme = \"file\"\nroot = \"home\"",
+            "name = \"a\"\nversion = \"1\"\nscheme = \"file\"\nnetwork = true",
+        ] {
+            assert!(PluginManifest::from_toml(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn scan() {
+        let user = plugins_dir(
+            "user",
+            &[("local", MANIFEST), ("broken", "name = 1"), ("bare", "")],
+        );
+        let system = plugins_dir(
+            "system",
+            &[
+                ("local", MANIFEST),
+                ("s3", "name = \"s3\"\nversion = \"1\"\nscheme = \"s3\""),
+            ],
+        );
+        let registry = PluginRegistry::scan(&[user.clone(), system.clone(), user.join("missing")]);
+
+        fs::remove_dir_all(&user).unwrap();
+        fs::remove_dir_all(&system).unwrap();
+
+        let names = registry
+            .plugins()
+            .iter()
+            .map(|plugin| plugin.manifest.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["local", "s3"]);
+        assert_eq!(
+            registry.find("local").unwrap().path.as_str(),
+            user.join("local.wasm").to_str().unwrap()
+        );
+        assert_eq!(registry.for_scheme("s3").unwrap().root().as_str(), "/");
+        assert_eq!(registry.errors().len(), 2);
+    }
+
+    #[test]
+    fn missing_permissions() {
+        let dir = plugins_dir("permissions", &[("local", MANIFEST)]);
+        let registry = PluginRegistry::scan(slice::from_ref(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+        let plugin = registry.find("local").unwrap();
+
+        assert_eq!(
+            plugin.missing_permissions(&PluginPolicy::default()),
+            vec![Permission::Folders, Permission::Clocks]
+        );
+        let policy = PluginPolicy {
+            preopens: vec![Preopen {
+                host: "/home/user".to_string(),
+                guest: "/".to_string(),
+                write: false,
+            }],
+            clocks: true,
+            ..PluginPolicy::default()
+        };
+        assert!(plugin.missing_permissions(&policy).is_empty());
+    }
+
+    #[test]
+    fn reload_changed() {
+        let dir = plugins_dir(
+            "reload",
+            &[(
+                "junkyard_reload",
+                "name = \"reload\"\nversion = \"1\"\nscheme = \"test\"",
+            )],
+        );
+        let component = dir.join("junkyard_reload.wasm");
+        let install = |target: &str| {
+            let wasm = TestPlugin::new()
+                .with(
+                    "[method]filesystem.read-link",
+                    return_string(1024, target.len() as i32),
+                )
+                .with_data(1024, target)
+                .build();
+            fs::write(&component, wasm).unwrap();
+        };
+        install("first");
+        let registry = PluginRegistry::scan(slice::from_ref(&dir));
+        let plugin = registry.find("reload").unwrap();
+        let vfs = plugin
+            .load()
+            .unwrap()
+            .mount(&MountConfig::new("test:///"))
+            .unwrap();
+        let path = AbsolutePath::new("/link");
+        assert!(registry.reload_changed().is_empty());
+
+        install("second");
+        let reloaded = registry.reload_changed();
+        assert_eq!(reloaded.len(), 1);
+        assert_eq!(reloaded[0].0, "reload");
+        assert!(reloaded[0].1.is_ok());
+        assert_eq!(vfs.read_link(&path), Ok("second".to_string()));
+        assert!(registry.reload_changed().is_empty());
+
+        // the previous version keeps running
+        fs::write(&component, "not a component").unwrap();
+        let reloaded = registry.reload_changed();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(reloaded[0].1.is_err());
+        assert_eq!(vfs.read_link(&path), Ok("second".to_string()));
+        assert!(registry.reload_changed().is_empty());
+    }
+}
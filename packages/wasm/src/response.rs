@@ -0,0 +1,88 @@
+use vfs::{AbsolutePath, DirEntry, VfsError, VfsResult};
+use wasm_vfs_api::PluginInfo;
+
+/// Makes sure every path a plugin listed in `dir` is a direct child of it.
+pub(crate) fn check_children(dir: &AbsolutePath, entries: &[AbsolutePath]) -> VfsResult<()> {
+    match entries
+        .iter()
+        .find(|entry| entry.is_root() || entry.parent() != *dir)
+    {
+        Some(entry) => Err(VfsError::InvalidResponse(format!(
+            "{} is not in {}",
+            entry.as_str(),
+            dir.as_str()
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Makes sure every entry a plugin listed is a plain file name, that
+/// cannot point outside of the listed folder.
+pub(crate) fn check_names(entries: &[DirEntry]) -> VfsResult<()> {
+    match entries.iter().find(|entry| {
+        entry.name.is_empty() || entry.name == "." || entry.name == ".." || entry.name.contains('/')
+    }) {
+        Some(entry) => Err(VfsError::InvalidResponse(format!(
+            "{:?} is not a file name",
+            entry.name
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Makes sure the schemes a plugin claims can be used in URIs.
+pub(crate) fn check_info(info: &PluginInfo) -> VfsResult<()> {
+    match info.schemes.iter().find(|scheme| !is_scheme(scheme)) {
+        Some(scheme) => Err(VfsError::InvalidResponse(format!(
+            "{:?} is not a URI scheme",
+            scheme
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// A scheme starts with a letter, followed by letters, digits, `+`, `-`
+/// or `.`.
+pub(crate) fn is_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use vfs::{AbsolutePath, DirEntry, Kind, Stat, VfsError};
+
+    use super::{check_children, check_names};
+
+    fn entry(name: &str) -> DirEntry {
+        DirEntry {
+            name: name.to_string(),
+            stat: Stat::new(Kind::File, 0),
+        }
+    }
+
+    #[test]
+    fn children() {
+        let dir = AbsolutePath::new("/home");
+        assert!(check_children(&dir, &[dir.join("a"), dir.join("b")]).is_ok());
+        for entry in ["/", "/home", "/etc", "/home/a/b"] {
+            assert!(matches!(
+                check_children(&dir, &[AbsolutePath::new(entry)]),
+                Err(VfsError::InvalidResponse(_))
+            ));
+        }
+        assert!(check_children(&AbsolutePath::new("/"), &[AbsolutePath::new("/etc")]).is_ok());
+    }
+
+    #[test]
+    fn names() {
+        assert!(check_names(&[entry("a"), entry(".hidden"), entry("..a")]).is_ok());
+        for name in ["", ".", "..", "../etc", "a/b"] {
+            assert!(matches!(
+                check_names(&[entry(name)]),
+                Err(VfsError::InvalidResponse(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock, Weak},
+};
+
+use anyhow::{Context, Result};
+use vfs::AbsolutePath;
+use wasm_vfs_api::{VfsExportsPre, VfsPlugin};
+use wasmtime::{
+    component::{Component, Linker},
+    Config, Engine, InstanceAllocationStrategy, PoolingAllocationConfig,
+};
+
+use crate::{
+    epoch::EpochTicker, instance::lock, log::PluginLogger, secret::PromptSlot,
+    storage::PluginStorage, ComponentCache, PluginLimits, PluginLog, PluginPolicy, PluginServices,
+    SecretPrompt, TrustedKeys, WasmPlugin, WasmVfsState,
+};
+
+/// Memories and tables reserved for every plugin instance by the pooling
+/// allocator, enough for a plugin and the adapter that gives it WASI.
+const MEMORIES_PER_INSTANCE: u32 = 4;
+
+/// The storage of each plugin by storage folder and plugin name.
+type Storages = HashMap<(Option<PathBuf>, String), Weak<PluginStorage>>;
+
+/// The engine that compiles and runs plugins, together with what every
+/// plugin needs from it. Cloning a runtime is cheap, and all the plugins
+/// loaded through clones of a runtime share its engine and its log.
+#[derive(Clone)]
+pub struct PluginRuntime {
+    engine: Engine,
+    linker: Arc<Linker<WasmVfsState>>,
+    ticker: Arc<EpochTicker>,
+    log: Arc<PluginLog>,
+    /// where plugins keep values between sessions
+    storage_dir: Option<PathBuf>,
+    /// the storage of the plugins in use, by folder and name, so that the
+    /// versions of a reloaded plugin do not overwrite each other's values
+    storages: Arc<Mutex<Storages>>,
+    prompt: PromptSlot,
+    /// what plugins have to be signed with before they are loaded
+    trusted_keys: Arc<TrustedKeys>,
+}
+
+impl PluginRuntime {
+    pub fn new() -> Result<PluginRuntime> {
+        PluginRuntime::with_config(config())
+    }
+
+    /// A runtime that keeps memory for `instances` plugin instances
+    /// reserved up front, which makes starting instances cheaper. Plugins
+    /// loaded into it may not use more than `limits` allow, whatever their
+    /// policy says.
+    pub fn with_pooling_allocator(instances: u32, limits: &PluginLimits) -> Result<PluginRuntime> {
+        let mut pooling = PoolingAllocationConfig::default();
+        pooling
+            .total_component_instances(instances)
+            .total_core_instances(instances.saturating_mul(limits.max_instances))
+            .total_memories(instances.saturating_mul(MEMORIES_PER_INSTANCE))
+            .total_tables(instances.saturating_mul(MEMORIES_PER_INSTANCE))
+            .max_memory_size(limits.max_memory_bytes as usize)
+            .table_elements(limits.max_table_elements as usize);
+        let mut config = config();
+        config.allocation_strategy(InstanceAllocationStrategy::Pooling(pooling));
+        PluginRuntime::with_config(config)
+    }
+
+    fn with_config(config: Config) -> Result<PluginRuntime> {
+        let engine = Engine::new(&config)?;
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::add_to_linker_async(&mut linker)?;
+        VfsPlugin::add_to_linker(&mut linker, |s| s)?;
+        Ok(PluginRuntime {
+            ticker: Arc::new(EpochTicker::start(engine.clone())),
+            engine,
+            linker: Arc::new(linker),
+            log: Arc::new(PluginLog::default()),
+            storage_dir: env::home_dir().map(|home| home.join(".junkyard/storage")),
+            storages: Arc::default(),
+            prompt: PromptSlot::default(),
+            trusted_keys: Arc::new(TrustedKeys::default()),
+        })
+    }
+
+    /// The runtime shared by everything in the process that does not need
+    /// one of its own, it checks signatures with the keys in
+    /// `~/.junkyard/trusted_keys.toml`.
+    pub fn global() -> Result<PluginRuntime> {
+        static GLOBAL: OnceLock<PluginRuntime> = OnceLock::new();
+        if let Some(runtime) = GLOBAL.get() {
+            return Ok(runtime.clone());
+        }
+        let runtime = PluginRuntime::new()?.with_trusted_keys(TrustedKeys::in_home()?);
+        Ok(GLOBAL.get_or_init(|| runtime).clone())
+    }
+
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    /// Plugins loaded from now on keep their values in `dir` instead of
+    /// `~/.junkyard/storage`.
+    pub fn with_storage_dir(mut self, dir: impl Into<PathBuf>) -> PluginRuntime {
+        self.storage_dir = Some(dir.into());
+        self
+    }
+
+    /// Plugins loaded from now on have to be signed with `keys`, as far as
+    /// their policy requires.
+    pub fn with_trusted_keys(mut self, keys: TrustedKeys) -> PluginRuntime {
+        self.trusted_keys = Arc::new(keys);
+        self
+    }
+
+    /// Lets the plugins of the runtime ask the user for secrets through
+    /// `prompt`, including the ones that are already loaded. Without a
+    /// prompt their questions stay unanswered.
+    pub fn set_secret_prompt(&self, prompt: Arc<dyn SecretPrompt>) {
+        *self
+            .prompt
+            .write()
+            .unwrap_or_else(|error| error.into_inner()) = Some(prompt);
+    }
+
+    /// What the plugins loaded into the runtime logged, tagged with the
+    /// name they were loaded as.
+    pub fn log(&self) -> &Arc<PluginLog> {
+        &self.log
+    }
+
+    /// Loads the plugin stored at `path`, named after its file, compiled
+    /// components are cached in `~/.junkyard/cache` when there is a home
+    /// folder.
+    pub fn load(&self, path: &AbsolutePath, policy: &PluginPolicy) -> Result<WasmPlugin> {
+        self.load_with_cache(path, policy, ComponentCache::in_home().as_ref())
+    }
+
+    pub fn load_with_cache(
+        &self,
+        path: &AbsolutePath,
+        policy: &PluginPolicy,
+        cache: Option<&ComponentCache>,
+    ) -> Result<WasmPlugin> {
+        let name = Path::new(path.as_str()).file_stem().unwrap_or_default();
+        self.load_as(&name.to_string_lossy(), path, policy, cache)
+    }
+
+    /// Loads the plugin stored at `path` as `name`, which tags what it logs
+    /// and names its storage.
+    pub fn load_as(
+        &self,
+        name: &str,
+        path: &AbsolutePath,
+        policy: &PluginPolicy,
+        cache: Option<&ComponentCache>,
+    ) -> Result<WasmPlugin> {
+        let wasm = fs::read(path.as_str())
+            .with_context(|| format!("unable to read the plugin {}", path.as_str()))?;
+        // nothing is compiled or taken from the cache before this
+        self.trusted_keys.check(
+            Path::new(path.as_str()),
+            &wasm,
+            &PluginLogger::new(name, self.log.clone()),
+        )?;
+        let component = match cache {
+            Some(cache) => cache.load(&self.engine, &wasm)?,
+            None => Component::new(&self.engine, &wasm)?,
+        };
+        self.prepare(name, &component, policy)
+    }
+
+    pub(crate) fn prepare(
+        &self,
+        name: &str,
+        component: &Component,
+        policy: &PluginPolicy,
+    ) -> Result<WasmPlugin> {
+        // imports are resolved once here instead of on every instantiation
+        let pre = VfsExportsPre::new(&self.linker, component)?;
+        Ok(WasmPlugin::new(
+            pre,
+            policy,
+            PluginServices {
+                logger: PluginLogger::new(name, self.log.clone()),
+                storage: self.storage(name, policy.limits.max_storage_bytes),
+                prompt: self.prompt.clone(),
+            },
+            self.ticker.clone(),
+        ))
+    }
+
+    /// The storage of the plugin `name`, shared with the versions of it
+    /// that are still in use.
+    fn storage(&self, name: &str, quota: u64) -> Arc<PluginStorage> {
+        let mut storages = lock(&self.storages);
+        storages.retain(|_, storage| storage.strong_count() > 0);
+        let key = (self.storage_dir.clone(), name.to_string());
+        if let Some(storage) = storages.get(&key).and_then(Weak::upgrade) {
+            storage.set_quota(quota);
+            return storage;
+        }
+        let storage = Arc::new(PluginStorage::new(self.storage_dir.as_deref(), name, quota));
+        storages.insert(key, Arc::downgrade(&storage));
+        storage
+    }
+}
+
+fn config() -> Config {
+    let mut config = Config::default();
+    config.wasm_component_model(true);
+    config.epoch_interruption(true);
+    config.async_support(true);
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use vfs::{AbsolutePath, Vfs, VfsError};
+    use wasm_vfs_api::VERSION;
+    use wasmtime::Engine;
+
+    use super::PluginRuntime;
+    use crate::{
+        test_plugin::{return_string, TestPlugin, WIT},
+        PluginLimits, PluginPolicy,
+    };
+
+    #[test]
+    fn global_engine() {
+        let first = PluginRuntime::global().unwrap();
+        let second = PluginRuntime::global().unwrap();
+        assert!(Engine::same(first.engine(), second.engine()));
+    }
+
+    #[test]
+    fn pooling_allocator() {
+        let limits = PluginLimits {
+            max_memory_bytes: 16 * 1024 * 1024,
+            ..PluginLimits::default()
+        };
+        let runtime = PluginRuntime::with_pooling_allocator(4, &limits).unwrap();
+        let policy = PluginPolicy {
+            limits,
+            ..PluginPolicy::default()
+        };
+        let vfs = TestPlugin::new()
+            .with("[method]filesystem.read-link", return_string(1024, 6))
+            .with_data(1024, "target")
+            .start_in(&runtime, &policy)
+            .unwrap();
+        assert_eq!(
+            vfs.read_link(&AbsolutePath::new("/link")),
+            Ok("target".to_string())
+        );
+    }
+
+    #[test]
+    fn interface_versions() {
+        // built against an older interface, without rename and symlinks
+        let older = WIT
+            .lines()
+            .filter(|line| {
+                let line = line.trim_start();
+                !line.starts_with("rename:") && !line.starts_with("create-symlink:")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let vfs = TestPlugin::new()
+            .against(older)
+            .with("[method]filesystem.read-link", return_string(1024, 6))
+            .with_data(1024, "target")
+            .start(&PluginPolicy::default())
+            .unwrap();
+        let path = AbsolutePath::new("/link");
+        assert_eq!(vfs.read_link(&path), Ok("target".to_string()));
+        assert_eq!(vfs.rename(&path, &path), Err(VfsError::Unsupported));
+        assert_eq!(
+            vfs.create_symlink("target", &path),
+            Err(VfsError::Unsupported)
+        );
+
+        for (version, message) in [
+            (
+                "@2.0.0",
+                "version 2.0.0 of junkyard-vfs:vfs-plugin, which is not compatible",
+            ),
+            ("", "unversioned junkyard-vfs:vfs-plugin"),
+        ] {
+            let wit = WIT.replacen(&format!("@{}", VERSION), version, 1);
+            let error = TestPlugin::new()
+                .against(wit)
+                .start(&PluginPolicy::default())
+                .err()
+                .unwrap();
+            assert!(error.to_string().contains(message), "{}", error);
+        }
+    }
+}
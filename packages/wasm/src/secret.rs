@@ -0,0 +1,190 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use async_trait::async_trait;
+
+use crate::instance::lock;
+
+/// What a plugin asks the user for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecretRequest {
+    /// The name of the plugin that asks.
+    pub plugin: String,
+    /// What the plugin is mounted on.
+    pub uri: String,
+    pub title: String,
+    pub description: String,
+}
+
+/// The user's answer to a [`SecretRequest`]. It does not implement `Debug`
+/// so that it never ends up in a log.
+#[derive(Clone, PartialEq)]
+pub struct Secret {
+    pub value: String,
+    /// Whether the mount is given the same answer without asking again,
+    /// until it is unmounted.
+    pub remember: bool,
+}
+
+/// Asks the user for the secrets plugins need, see
+/// [`crate::PluginRuntime::set_secret_prompt`].
+#[async_trait]
+pub trait SecretPrompt: Send + Sync {
+    /// Returns `None` if the user refused to answer.
+    async fn prompt_secret(&self, request: SecretRequest) -> Option<Secret>;
+}
+
+/// The prompt of a runtime, plugins loaded before it was set use it too.
+pub(crate) type PromptSlot = Arc<RwLock<Option<Arc<dyn SecretPrompt>>>>;
+
+/// The secrets of one mount, they are only kept in memory.
+pub(crate) struct MountSecrets {
+    prompt: PromptSlot,
+    plugin: String,
+    uri: String,
+    /// the answers the user asked to remember, by title and description
+    remembered: Mutex<HashMap<(String, String), String>>,
+}
+
+impl MountSecrets {
+    pub(crate) fn new(prompt: PromptSlot, plugin: &str, uri: &str) -> MountSecrets {
+        MountSecrets {
+            prompt,
+            plugin: plugin.to_string(),
+            uri: uri.to_string(),
+            remembered: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Asks the user, unless they asked to remember the answer. Without a
+    /// prompt there is nobody to ask and the plugin gets no answer.
+    pub(crate) async fn prompt(&self, title: String, description: String) -> Option<String> {
+        let key = (title, description);
+        if let Some(secret) = lock(&self.remembered).get(&key) {
+            return Some(secret.clone());
+        }
+        let prompt = self
+            .prompt
+            .read()
+            .unwrap_or_else(|error| error.into_inner())
+            .clone()?;
+        let secret = prompt
+            .prompt_secret(SecretRequest {
+                plugin: self.plugin.clone(),
+                uri: self.uri.clone(),
+                title: key.0.clone(),
+                description: key.1.clone(),
+            })
+            .await?;
+        if secret.remember {
+            lock(&self.remembered).insert(key, secret.value.clone());
+        }
+        Some(secret.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc, RwLock,
+        },
+        thread,
+        time::Duration,
+    };
+
+    use async_trait::async_trait;
+    use vfs::{block_on, AbsolutePath, Vfs};
+    use wasm_encoder::Instruction;
+
+    use super::{MountSecrets, Secret, SecretPrompt, SecretRequest};
+    use crate::{
+        test_plugin::{mem, Body, TestPlugin, PROMPT_SECRET, RET_AREA},
+        PluginLimits, PluginPolicy, PluginRuntime,
+    };
+
+    /// Answers with the title after a while, and counts the questions.
+    struct SlowUser {
+        asked: AtomicU32,
+        remember: bool,
+    }
+
+    #[async_trait]
+    impl SecretPrompt for SlowUser {
+        async fn prompt_secret(&self, request: SecretRequest) -> Option<Secret> {
+            self.asked.fetch_add(1, Ordering::Relaxed);
+            thread::sleep(Duration::from_millis(100));
+            Some(Secret {
+                value: format!("{} for {}", request.title, request.uri),
+                remember: self.remember,
+            })
+        }
+    }
+
+    fn user(remember: bool) -> Arc<SlowUser> {
+        Arc::new(SlowUser {
+            asked: AtomicU32::new(0),
+            remember,
+        })
+    }
+
+    #[test]
+    fn remembered() {
+        for remember in [false, true] {
+            let user = user(remember);
+            let prompt: Arc<dyn SecretPrompt> = user.clone();
+            let secrets = MountSecrets::new(Arc::new(RwLock::new(Some(prompt))), "s3", "s3:///");
+            for _ in 0..2 {
+                assert_eq!(
+                    block_on(secrets.prompt("key".to_string(), String::new())),
+                    Some("key for s3:///".to_string())
+                );
+            }
+            let expected = if remember { 1 } else { 2 };
+            assert_eq!(user.asked.load(Ordering::Relaxed), expected);
+        }
+        let nobody = MountSecrets::new(Arc::new(RwLock::new(None)), "s3", "s3:///");
+        assert_eq!(
+            block_on(nobody.prompt("key".to_string(), String::new())),
+            None
+        );
+    }
+
+    #[test]
+    fn waiting_for_the_user_is_not_a_timeout() {
+        // read-link answers with the secret
+        let read_link = vec![
+            Instruction::I32Const(1024),
+            Instruction::I32Const(5),
+            Instruction::I32Const(1024),
+            Instruction::I32Const(0),
+            Instruction::I32Const(RET_AREA),
+            Instruction::Call(PROMPT_SECRET),
+            Instruction::I32Const(RET_AREA),
+            Instruction::I32Const(0),
+            Instruction::I32Store8(mem(0, 0)),
+            Instruction::I32Const(RET_AREA),
+        ];
+        let runtime = PluginRuntime::new().unwrap();
+        runtime.set_secret_prompt(user(false));
+        let policy = PluginPolicy {
+            limits: PluginLimits {
+                call_timeout_ms: 20,
+                ..PluginLimits::default()
+            },
+            ..PluginPolicy::default()
+        };
+        let vfs = TestPlugin::new()
+            .with("[method]filesystem.read-link", Body::Code(read_link))
+            .with_data(1024, "token")
+            .start_in(&runtime, &policy)
+            .unwrap();
+        assert_eq!(
+            vfs.read_link(&AbsolutePath::new("/link")),
+            Ok("token for test:///".to_string())
+        );
+    }
+}
@@ -0,0 +1,300 @@
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::Deserialize;
+
+use crate::{log::PluginLogger, LogLevel};
+
+/// The custom section a signature is embedded in, it has to be the last
+/// section of the component and signs everything before it.
+pub const SIGNATURE_SECTION: &str = "junkyard-signature";
+
+/// What happens to plugins that are not signed by a trusted key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignaturePolicy {
+    /// They are refused.
+    #[default]
+    Enforce,
+    /// They are loaded, with a warning in the plugin log.
+    Warn,
+    /// Signatures are not checked.
+    Off,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TrustedKeysFile {
+    #[serde(default)]
+    policy: SignaturePolicy,
+    /// hex encoded ed25519 public keys, by name
+    #[serde(default)]
+    keys: BTreeMap<String, String>,
+}
+
+/// The keys plugins have to be signed with, from
+/// `~/.junkyard/trusted_keys.toml`.
+///
+/// A plugin is signed by an ed25519 signature of its `.wasm` file, either
+/// detached in a `<plugin name>.wasm.sig` file next to it, hex encoded, or
+/// embedded in a [`SIGNATURE_SECTION`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrustedKeys {
+    pub policy: SignaturePolicy,
+    keys: Vec<(String, VerifyingKey)>,
+}
+
+impl Default for TrustedKeys {
+    /// Without a trusted keys file nothing is checked.
+    fn default() -> Self {
+        TrustedKeys {
+            policy: SignaturePolicy::Off,
+            keys: vec![],
+        }
+    }
+}
+
+impl TrustedKeys {
+    pub fn from_toml(source: &str) -> Result<TrustedKeys> {
+        let file: TrustedKeysFile = toml::from_str(source)?;
+        let keys = file
+            .keys
+            .into_iter()
+            .map(|(name, key)| {
+                let key = decode(&key)
+                    .and_then(|bytes| Ok(VerifyingKey::from_bytes(&bytes)?))
+                    .with_context(|| format!("invalid key {}", name))?;
+                Ok((name, key))
+            })
+            .collect::<Result<_>>()?;
+        Ok(TrustedKeys {
+            policy: file.policy,
+            keys,
+        })
+    }
+
+    /// The keys in `~/.junkyard/trusted_keys.toml`, or the default ones if
+    /// there is no such file.
+    pub fn in_home() -> Result<TrustedKeys> {
+        match env::home_dir() {
+            Some(home) => TrustedKeys::from_file(&home.join(".junkyard/trusted_keys.toml")),
+            None => Ok(TrustedKeys::default()),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<TrustedKeys> {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                return Ok(TrustedKeys::default())
+            }
+            Err(error) => return Err(error.into()),
+        };
+        TrustedKeys::from_toml(&source)
+            .with_context(|| format!("invalid trusted keys file {}", path.display()))
+    }
+
+    /// Checks the signature of the plugin stored at `path`, whose content is
+    /// `wasm`, before it is compiled or taken from the cache.
+    pub(crate) fn check(&self, path: &Path, wasm: &[u8], logger: &PluginLogger) -> Result<()> {
+        if self.policy == SignaturePolicy::Off {
+            return Ok(());
+        }
+        match self.signer(path, wasm) {
+            Ok(signer) => {
+                logger.log(LogLevel::Info, &format!("signed by {}", signer));
+                Ok(())
+            }
+            Err(error) if self.policy == SignaturePolicy::Warn => {
+                logger.log(LogLevel::Warn, &format!("{:#}, loaded anyway", error));
+                Ok(())
+            }
+            Err(error) => Err(error.context(format!("refused to load {}", path.display()))),
+        }
+    }
+
+    /// The name of the trusted key that signed the plugin.
+    fn signer(&self, path: &Path, wasm: &[u8]) -> Result<&str> {
+        let (message, signature) = match fs::read_to_string(detached_path(path)) {
+            Ok(signature) => {
+                let signature = decode(signature.trim()).context("invalid signature file")?;
+                (wasm, Signature::from_bytes(&signature))
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                let (message, signature) =
+                    embedded_signature(wasm).ok_or_else(|| anyhow!("the plugin is not signed"))?;
+                let signature =
+                    Signature::from_slice(signature).context("invalid embedded signature")?;
+                (message, signature)
+            }
+            Err(error) => return Err(error.into()),
+        };
+        self.keys
+            .iter()
+            .find(|(_, key)| key.verify_strict(message, &signature).is_ok())
+            .map(|(name, _)| name.as_str())
+            .ok_or_else(|| anyhow!("the plugin is not signed by a trusted key"))
+    }
+}
+
+fn detached_path(path: &Path) -> PathBuf {
+    let mut detached = path.as_os_str().to_owned();
+    detached.push(".sig");
+    PathBuf::from(detached)
+}
+
+fn decode<const N: usize>(hex: &str) -> Result<[u8; N]> {
+    let bytes = hex::decode(hex)?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| anyhow!("expected {} bytes, got {}", N, bytes.len()))
+}
+
+/// Splits a component whose last section is a [`SIGNATURE_SECTION`] into
+/// what is signed and the signature.
+fn embedded_signature(wasm: &[u8]) -> Option<(&[u8], &[u8])> {
+    // the magic number and the version come before the sections
+    let mut offset = 8;
+    let mut last = None;
+    while offset < wasm.len() {
+        let (size, len) = leb128(wasm.get(offset + 1..)?)?;
+        let contents = wasm.get(offset + 1 + len..offset + 1 + len + size)?;
+        last = Some((offset, wasm[offset], contents));
+        offset += 1 + len + size;
+    }
+    let (start, id, contents) = last?;
+    let (name_len, len) = leb128(contents)?;
+    let name = contents.get(len..len + name_len)?;
+    (id == 0 && name == SIGNATURE_SECTION.as_bytes())
+        .then(|| (&wasm[..start], &contents[len + name_len..]))
+}
+
+/// An unsigned LEB128 number, and how many bytes it takes.
+fn leb128(bytes: &[u8]) -> Option<(usize, usize)> {
+    let mut value = 0;
+    for (i, byte) in bytes.iter().take(5).enumerate() {
+        value |= ((byte & 0x7f) as usize) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs};
+
+    use ed25519_dalek::{Signer, SigningKey};
+    use vfs::AbsolutePath;
+
+    use super::{SignaturePolicy, TrustedKeys, SIGNATURE_SECTION};
+    use crate::{
+        test_plugin::TestPlugin, ComponentCache, LogLevel, PluginPolicy, PluginRuntime,
+        DEFAULT_MAX_CACHE_BYTES,
+    };
+
+    fn keys(policy: &str, trusted: &SigningKey) -> TrustedKeys {
+        TrustedKeys::from_toml(&format!(
+            "policy = \"{}\"\n[keys]\nteam = \"{}\"",
+            policy,
+            hex::encode(trusted.verifying_key().as_bytes())
+        ))
+        .unwrap()
+    }
+
+    /// Appends a signature section, small enough for its sizes to take a
+    /// byte each.
+    fn embed(wasm: &[u8], signer: &SigningKey) -> Vec<u8> {
+        let signature = signer.sign(wasm).to_bytes();
+        let mut signed = wasm.to_vec();
+        signed.extend([
+            0,
+            (1 + SIGNATURE_SECTION.len() + signature.len()) as u8,
+            SIGNATURE_SECTION.len() as u8,
+        ]);
+        signed.extend(SIGNATURE_SECTION.as_bytes());
+        signed.extend(signature);
+        signed
+    }
+
+    #[test]
+    fn trusted_keys() {
+        let trusted = SigningKey::from_bytes(&[1; 32]);
+        assert_eq!(keys("warn", &trusted).policy, SignaturePolicy::Warn);
+        assert_eq!(
+            TrustedKeys::from_toml("").unwrap().policy,
+            SignaturePolicy::Enforce
+        );
+        assert!(TrustedKeys::from_toml("[keys]\nteam = \"abcd\"").is_err());
+        assert!(TrustedKeys::from_toml("policy = \"sometimes\"").is_err());
+    }
+
+    #[test]
+    fn signed_plugins() {
+        let dir = temp_dir().join("junkyard_signatures");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let trusted = SigningKey::from_bytes(&[1; 32]);
+        let stranger = SigningKey::from_bytes(&[2; 32]);
+        let wasm = TestPlugin::new().build();
+        let mut tampered = embed(&wasm, &trusted);
+        tampered[8] ^= 1;
+        let install = |name: &str, wasm: &[u8], detached: Option<&SigningKey>| {
+            let path = dir.join(format!("{}.wasm", name));
+            fs::write(&path, wasm).unwrap();
+            if let Some(signer) = detached {
+                let signature = hex::encode(signer.sign(wasm).to_bytes());
+                fs::write(dir.join(format!("{}.wasm.sig", name)), signature).unwrap();
+            }
+            AbsolutePath::new(path.to_string_lossy())
+        };
+        let policy = PluginPolicy::default();
+
+        let runtime = PluginRuntime::new()
+            .unwrap()
+            .with_trusted_keys(keys("enforce", &trusted));
+        for (name, wasm, detached, loads) in [
+            ("detached", wasm.clone(), Some(&trusted), true),
+            ("embedded", embed(&wasm, &trusted), None, true),
+            ("unsigned", wasm.clone(), None, false),
+            ("untrusted", embed(&wasm, &stranger), None, false),
+            ("stranger", wasm.clone(), Some(&stranger), false),
+            ("tampered", tampered, None, false),
+        ] {
+            let path = install(name, &wasm, detached);
+            let loaded = runtime.load_with_cache(&path, &policy, None);
+            assert_eq!(loaded.is_ok(), loads, "{}", name);
+        }
+
+        // a compiled plugin in the cache does not skip the check
+        let cache = ComponentCache::new(dir.join("cache"), DEFAULT_MAX_CACHE_BYTES);
+        let path = install("cached", &wasm, Some(&trusted));
+        assert!(runtime
+            .load_with_cache(&path, &policy, Some(&cache))
+            .is_ok());
+        fs::remove_file(dir.join("cached.wasm.sig")).unwrap();
+        let error = runtime
+            .load_with_cache(&path, &policy, Some(&cache))
+            .err()
+            .unwrap();
+        assert!(format!("{:#}", error).contains("the plugin is not signed"));
+
+        let runtime = PluginRuntime::new()
+            .unwrap()
+            .with_trusted_keys(keys("warn", &trusted));
+        let path = install("warned", &wasm, None);
+        assert!(runtime.load_with_cache(&path, &policy, None).is_ok());
+        fs::remove_dir_all(&dir).unwrap();
+        let records = runtime.log().records();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].plugin, "warned");
+        assert_eq!(records[0].level, LogLevel::Warn);
+        assert!(records[0].message.contains("not signed"));
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use wasm_vfs_api::StorageError;
+
+use crate::instance::lock;
+
+/// The values a plugin keeps between sessions, in
+/// `<storage folder>/<plugin name>.toml`. All the mounts of a plugin share
+/// them, and so do the versions of a plugin that is reloaded, see
+/// [`crate::PluginRuntime`].
+pub(crate) struct PluginStorage {
+    /// `None` if there is nowhere to store values, they are kept in memory
+    /// until junkyard exits
+    path: Option<PathBuf>,
+    /// how many bytes the keys and values may take together, set by the
+    /// policy of the version loaded last
+    quota: AtomicU64,
+    /// read from the file the first time the plugin uses its storage
+    values: Mutex<Option<BTreeMap<String, Vec<u8>>>>,
+}
+
+impl PluginStorage {
+    pub(crate) fn new(dir: Option<&Path>, plugin: &str, quota: u64) -> PluginStorage {
+        PluginStorage {
+            path: dir.map(|dir| dir.join(format!("{}.toml", plugin))),
+            quota: AtomicU64::new(quota),
+            values: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn set_quota(&self, quota: u64) {
+        self.quota.store(quota, Ordering::SeqCst);
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<Vec<u8>> {
+        self.with_values(|values| Ok(values.get(key).cloned()))
+            .ok()
+            .flatten()
+    }
+
+    pub(crate) fn set(&self, key: &str, value: &[u8]) -> Result<(), StorageError> {
+        self.with_values(|values| {
+            let replaced = values.get(key).map_or(0, |old| size(key, old));
+            if used(values) - replaced + size(key, value) > self.quota.load(Ordering::SeqCst) {
+                return Err(StorageError::QuotaExceeded);
+            }
+            let old = values.insert(key.to_string(), value.to_vec());
+            self.save(values).inspect_err(|_| match old {
+                Some(old) => {
+                    values.insert(key.to_string(), old);
+                }
+                None => {
+                    values.remove(key);
+                }
+            })
+        })
+    }
+
+    pub(crate) fn delete(&self, key: &str) -> Result<(), StorageError> {
+        self.with_values(|values| match values.remove(key) {
+            Some(old) => self.save(values).inspect_err(|_| {
+                values.insert(key.to_string(), old);
+            }),
+            None => Ok(()),
+        })
+    }
+
+    pub(crate) fn list(&self) -> Vec<String> {
+        self.with_values(|values| Ok(values.keys().cloned().collect()))
+            .unwrap_or_default()
+    }
+
+    fn with_values<T>(
+        &self,
+        f: impl FnOnce(&mut BTreeMap<String, Vec<u8>>) -> Result<T, StorageError>,
+    ) -> Result<T, StorageError> {
+        let mut values = lock(&self.values);
+        if values.is_none() {
+            *values = Some(self.load()?);
+        }
+        f(values.as_mut().unwrap())
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, Vec<u8>>, StorageError> {
+        let Some(path) = &self.path else {
+            return Ok(BTreeMap::new());
+        };
+        match fs::read_to_string(path) {
+            Ok(source) => toml::from_str(&source).map_err(|error| {
+                StorageError::Io(format!("invalid storage {}: {}", path.display(), error))
+            }),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(error) => Err(StorageError::Io(error.to_string())),
+        }
+    }
+
+    /// Writes the values next to the file and renames them over it, so
+    /// that a crash never leaves half of them behind.
+    fn save(&self, values: &BTreeMap<String, Vec<u8>>) -> Result<(), StorageError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let source =
+            toml::to_string(values).map_err(|error| StorageError::Io(error.to_string()))?;
+        let temporary = path.with_extension(format!("toml.{}", process::id()));
+        let written = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| File::create(&temporary))
+            .and_then(|mut file| {
+                file.write_all(source.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|_| fs::rename(&temporary, path));
+        if written.is_err() {
+            let _ = fs::remove_file(&temporary);
+        }
+        written.map_err(|error| StorageError::Io(error.to_string()))
+    }
+}
+
+fn size(key: &str, value: &[u8]) -> u64 {
+    (key.len() + value.len()) as u64
+}
+
+fn used(values: &BTreeMap<String, Vec<u8>>) -> u64 {
+    values.iter().map(|(key, value)| size(key, value)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env::temp_dir, fs};
+
+    use wasm_vfs_api::StorageError;
+
+    use super::PluginStorage;
+
+    #[test]
+    fn persisted() {
+        let dir = temp_dir().join("junkyard_storage_persisted");
+        let _ = fs::remove_dir_all(&dir);
+        let storage = PluginStorage::new(Some(&dir), "s3", 1024);
+        storage.set("token", b"secret").unwrap();
+        storage.set("region", b"eu").unwrap();
+        storage.set("region", b"us").unwrap();
+        storage.set("empty", b"").unwrap();
+        storage.delete("empty").unwrap();
+        storage.delete("missing").unwrap();
+
+        let reloaded = PluginStorage::new(Some(&dir), "s3", 1024);
+        let other = PluginStorage::new(Some(&dir), "zip", 1024);
+        let keys = reloaded.list();
+        let token = reloaded.get("token");
+        let region = reloaded.get("region");
+        let others = other.list();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(keys, vec!["region", "token"]);
+        assert_eq!(token, Some(b"secret".to_vec()));
+        assert_eq!(region, Some(b"us".to_vec()));
+        assert!(others.is_empty());
+    }
+
+    #[test]
+    fn quota() {
+        let storage = PluginStorage::new(None, "s3", 10);
+        storage.set("key", b"1234567").unwrap();
+        assert_eq!(storage.set("other", b""), Err(StorageError::QuotaExceeded));
+        // replacing a value only counts the difference
+        storage.set("key", b"7654321").unwrap();
+        assert_eq!(
+            storage.set("key", b"12345678"),
+            Err(StorageError::QuotaExceeded)
+        );
+        assert_eq!(storage.get("key"), Some(b"7654321".to_vec()));
+        storage.delete("key").unwrap();
+        storage.set("other", b"12345").unwrap();
+    }
+}
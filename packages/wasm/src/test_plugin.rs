@@ -0,0 +1,452 @@
+//! Builds plugin components for tests out of hand written core functions,
+//! so that tests can load plugins that trap, loop or lie on purpose.
+
+use std::collections::HashMap;
+
+use wasm_encoder::{
+    CodeSection, ConstExpr, DataSection, ExportKind, ExportSection, Function, FunctionSection,
+    GlobalSection, GlobalType, ImportSection, Instruction, MemArg, MemorySection, MemoryType,
+    Module, TypeSection, ValType,
+};
+use wasmtime::component::Component;
+use wit_component::{ComponentEncoder, StringEncoding};
+use wit_parser::{
+    abi::{AbiVariant, WasmType},
+    Handle, InterfaceId, LiftLowerAbi, ManglingAndAbi, Resolve, ResourceIntrinsic, Type,
+    TypeDefKind, TypeId, WasmExport, WasmExportKind, WasmImport, WorldItem, WorldKey,
+};
+
+use crate::{MountConfig, PluginPolicy, PluginRuntime, WasmPlugin, WasmVfs};
+
+pub(crate) const WIT: &str = include_str!("../../wasm_vfs_api/wit/vfs.wit");
+
+/// Where functions write their results.
+pub(crate) const RET_AREA: i32 = 16;
+/// Returned by `info`, the plugin-info stored there is all empty unless a
+/// test writes one.
+pub(crate) const INFO_AREA: i32 = 256;
+/// Where `cabi_realloc` starts to allocate from.
+const HEAP_START: i32 = 4096;
+
+// imported functions
+const RESOURCE_NEW_FILESYSTEM: u32 = 0;
+const RESOURCE_NEW_FILE: u32 = 1;
+const RESOURCE_DROP_ABSOLUTE_PATH: u32 = 2;
+/// `create-absolute-path(ptr, len) -> handle`
+pub(crate) const CREATE_ABSOLUTE_PATH: u32 = 3;
+/// `log(level, ptr, len)`
+pub(crate) const LOG: u32 = 4;
+/// `prompt-secret(title ptr, title len, description ptr, description len,
+/// ret ptr)`
+pub(crate) const PROMPT_SECRET: u32 = 5;
+/// `report-progress(done, total, message ptr, message len)`
+pub(crate) const REPORT_PROGRESS: u32 = 6;
+const IMPORTED_FUNCTIONS: u32 = 7;
+
+pub(crate) enum Body {
+    /// Executes `unreachable`.
+    Trap,
+    /// Never returns.
+    Loop,
+    /// Runs the instructions, which have to leave the result on the stack.
+    Code(Vec<Instruction<'static>>),
+}
+
+pub(crate) fn mem(offset: u64, align: u32) -> MemArg {
+    MemArg {
+        offset,
+        align,
+        memory_index: 0,
+    }
+}
+
+/// Stores `ok(resource)` in the return area, where `resource` is created
+/// by calling `resource_new`.
+fn return_resource(resource_new: u32) -> Vec<Instruction<'static>> {
+    vec![
+        Instruction::I32Const(RET_AREA),
+        Instruction::I32Const(0),
+        Instruction::I32Store8(mem(0, 0)),
+        Instruction::I32Const(RET_AREA),
+        Instruction::I32Const(1),
+        Instruction::Call(resource_new),
+        Instruction::I32Store(mem(4, 2)),
+        Instruction::I32Const(RET_AREA),
+    ]
+}
+
+/// Stores `ok(filesystem)` in the return area, like `init` does unless a
+/// test replaces it.
+pub(crate) fn return_filesystem() -> Vec<Instruction<'static>> {
+    return_resource(RESOURCE_NEW_FILESYSTEM)
+}
+
+/// Returns `ok(string)` for a string stored at `ptr`.
+pub(crate) fn return_string(ptr: i32, len: i32) -> Body {
+    Body::Code(return_slice(ptr, len))
+}
+
+/// Stores `ok` with a string or list of `len` elements at `ptr` in the
+/// return area.
+pub(crate) fn return_slice(ptr: i32, len: i32) -> Vec<Instruction<'static>> {
+    vec![
+        Instruction::I32Const(RET_AREA),
+        Instruction::I32Const(0),
+        Instruction::I32Store8(mem(0, 0)),
+        Instruction::I32Const(RET_AREA),
+        Instruction::I32Const(ptr),
+        Instruction::I32Store(mem(4, 2)),
+        Instruction::I32Const(RET_AREA),
+        Instruction::I32Const(len),
+        Instruction::I32Store(mem(8, 2)),
+        Instruction::I32Const(RET_AREA),
+    ]
+}
+
+pub(crate) struct TestPlugin {
+    bodies: HashMap<String, Body>,
+    data: Vec<(i32, Vec<u8>)>,
+    /// the interface the plugin is built against, [`WIT`] by default
+    wit: Option<String>,
+}
+
+impl TestPlugin {
+    /// A plugin that starts, describes itself and opens files, every other
+    /// call traps.
+    pub(crate) fn new() -> TestPlugin {
+        TestPlugin {
+            bodies: HashMap::new(),
+            data: vec![],
+            wit: None,
+        }
+        .with("init", Body::Code(return_filesystem()))
+        .with("info", Body::Code(vec![Instruction::I32Const(INFO_AREA)]))
+        .with(
+            "[method]filesystem.open",
+            Body::Code(return_resource(RESOURCE_NEW_FILE)),
+        )
+    }
+
+    pub(crate) fn with(mut self, function: &str, body: Body) -> TestPlugin {
+        self.bodies.insert(function.to_string(), body);
+        self
+    }
+
+    pub(crate) fn with_data(mut self, offset: i32, data: impl Into<Vec<u8>>) -> TestPlugin {
+        self.data.push((offset, data.into()));
+        self
+    }
+
+    /// Builds the plugin against `wit` instead of the host's interface, it
+    /// exports the functions `wit` declares.
+    pub(crate) fn against(mut self, wit: impl Into<String>) -> TestPlugin {
+        self.wit = Some(wit.into());
+        self
+    }
+
+    pub(crate) fn build(&self) -> Vec<u8> {
+        let mut resolve = Resolve::default();
+        let package = resolve
+            .push_str("vfs.wit", self.wit.as_deref().unwrap_or(WIT))
+            .unwrap();
+        let world = resolve.select_world(package, Some("vfs-plugin")).unwrap();
+        let mangling = ManglingAndAbi::Legacy(LiftLowerAbi::Sync);
+        let (host_key, host) = interface(&resolve.worlds[world].imports);
+        let (vfs_key, vfs) = interface(&resolve.worlds[world].exports);
+
+        let mut types = TypeSection::new();
+        let mut imports = ImportSection::new();
+        let mut functions = FunctionSection::new();
+        let mut exports = ExportSection::new();
+        let mut code = CodeSection::new();
+
+        for resource in ["filesystem", "file"] {
+            let (module, name) = resolve.wasm_import_name(
+                mangling,
+                WasmImport::ResourceIntrinsic {
+                    interface: Some(vfs_key),
+                    resource: resolve.interfaces[vfs].types[resource],
+                    intrinsic: ResourceIntrinsic::ExportedNew,
+                },
+            );
+            imports.import(
+                &module,
+                &name,
+                wasm_encoder::EntityType::Function(types.len()),
+            );
+            types.ty().function([ValType::I32], [ValType::I32]);
+        }
+        let absolute_path = resolve.interfaces[host].types["absolute-path"];
+        let (module, name) = resolve.wasm_import_name(
+            mangling,
+            WasmImport::ResourceIntrinsic {
+                interface: Some(host_key),
+                resource: absolute_path,
+                intrinsic: ResourceIntrinsic::ImportedDrop,
+            },
+        );
+        imports.import(
+            &module,
+            &name,
+            wasm_encoder::EntityType::Function(types.len()),
+        );
+        types.ty().function([ValType::I32], []);
+        let create_absolute_path = &resolve.interfaces[host].functions["create-absolute-path"];
+        let (module, name) = resolve.wasm_import_name(
+            mangling,
+            WasmImport::Func {
+                interface: Some(host_key),
+                func: create_absolute_path,
+            },
+        );
+        imports.import(
+            &module,
+            &name,
+            wasm_encoder::EntityType::Function(types.len()),
+        );
+        types
+            .ty()
+            .function([ValType::I32, ValType::I32], [ValType::I32]);
+        let log = &resolve.interfaces[host].functions["log"];
+        let (module, name) = resolve.wasm_import_name(
+            mangling,
+            WasmImport::Func {
+                interface: Some(host_key),
+                func: log,
+            },
+        );
+        imports.import(
+            &module,
+            &name,
+            wasm_encoder::EntityType::Function(types.len()),
+        );
+        types.ty().function([ValType::I32; 3], []);
+        let prompt_secret = &resolve.interfaces[host].functions["prompt-secret"];
+        let (module, name) = resolve.wasm_import_name(
+            mangling,
+            WasmImport::Func {
+                interface: Some(host_key),
+                func: prompt_secret,
+            },
+        );
+        imports.import(
+            &module,
+            &name,
+            wasm_encoder::EntityType::Function(types.len()),
+        );
+        types.ty().function([ValType::I32; 5], []);
+        let report_progress = &resolve.interfaces[host].functions["report-progress"];
+        let (module, name) = resolve.wasm_import_name(
+            mangling,
+            WasmImport::Func {
+                interface: Some(host_key),
+                func: report_progress,
+            },
+        );
+        imports.import(
+            &module,
+            &name,
+            wasm_encoder::EntityType::Function(types.len()),
+        );
+        types
+            .ty()
+            .function([ValType::I64, ValType::I64, ValType::I32, ValType::I32], []);
+
+        let mut index = IMPORTED_FUNCTIONS;
+        for function in resolve.interfaces[vfs].functions.values() {
+            let signature = resolve.wasm_signature(AbiVariant::GuestExport, function);
+            functions.function(types.len());
+            types.ty().function(
+                signature.params.iter().map(val_type),
+                signature.results.iter().map(val_type),
+            );
+            let name = resolve.wasm_export_name(
+                mangling,
+                WasmExport::Func {
+                    interface: Some(vfs_key),
+                    func: function,
+                    kind: WasmExportKind::Normal,
+                },
+            );
+            exports.export(&name, ExportKind::Func, index);
+            // like the generated bindings, release the borrowed paths
+            // before doing anything else
+            let mut flat = vec![];
+            let mut borrowed = vec![];
+            for (_, ty) in &function.params {
+                if is_borrow_of(&resolve, ty, absolute_path) {
+                    borrowed.push(flat.len() as u32);
+                }
+                resolve.push_flat(ty, &mut flat);
+            }
+            code.function(&body(&borrowed, self.bodies.get(&function.name)));
+            index += 1;
+        }
+        // files are only destroyed by the plugin if a test asks for it
+        if let Some(dtor) = self.bodies.get("[dtor]file") {
+            functions.function(types.len());
+            types.ty().function([ValType::I32], []);
+            let name = resolve.wasm_export_name(
+                mangling,
+                WasmExport::ResourceDtor {
+                    interface: vfs_key,
+                    resource: resolve.interfaces[vfs].types["file"],
+                },
+            );
+            exports.export(&name, ExportKind::Func, index);
+            code.function(&body(&[], Some(dtor)));
+            index += 1;
+        }
+
+        // cabi_realloc(old_ptr, old_size, align, new_size), a bump allocator
+        functions.function(types.len());
+        types.ty().function([ValType::I32; 4], [ValType::I32]);
+        exports.export("cabi_realloc", ExportKind::Func, index);
+        let mut realloc = Function::new([(1, ValType::I32)]);
+        for instruction in [
+            Instruction::GlobalGet(0),
+            Instruction::LocalGet(2),
+            Instruction::I32Add,
+            Instruction::I32Const(1),
+            Instruction::I32Sub,
+            Instruction::I32Const(0),
+            Instruction::LocalGet(2),
+            Instruction::I32Sub,
+            Instruction::I32And,
+            Instruction::LocalTee(4),
+            Instruction::LocalGet(3),
+            Instruction::I32Add,
+            Instruction::GlobalSet(0),
+            Instruction::LocalGet(4),
+            Instruction::End,
+        ] {
+            realloc.instruction(&instruction);
+        }
+        code.function(&realloc);
+
+        let mut memories = MemorySection::new();
+        memories.memory(MemoryType {
+            minimum: 2,
+            maximum: None,
+            memory64: false,
+            shared: false,
+            page_size_log2: None,
+        });
+        exports.export("memory", ExportKind::Memory, 0);
+        let mut globals = GlobalSection::new();
+        globals.global(
+            GlobalType {
+                val_type: ValType::I32,
+                mutable: true,
+                shared: false,
+            },
+            &ConstExpr::i32_const(HEAP_START),
+        );
+        let mut data = DataSection::new();
+        for (offset, bytes) in &self.data {
+            data.active(0, &ConstExpr::i32_const(*offset), bytes.iter().copied());
+        }
+
+        let mut module = Module::new();
+        module
+            .section(&types)
+            .section(&imports)
+            .section(&functions)
+            .section(&memories)
+            .section(&globals)
+            .section(&exports)
+            .section(&code)
+            .section(&data);
+        let mut bytes = module.finish();
+        wit_component::embed_component_metadata(&mut bytes, &resolve, world, StringEncoding::UTF8)
+            .unwrap();
+        ComponentEncoder::default()
+            .module(&bytes)
+            .unwrap()
+            .validate(true)
+            .encode()
+            .unwrap()
+    }
+
+    pub(crate) fn start(&self, policy: &PluginPolicy) -> anyhow::Result<WasmVfs> {
+        self.start_in(&PluginRuntime::new()?, policy)
+    }
+
+    pub(crate) fn start_in(
+        &self,
+        runtime: &PluginRuntime,
+        policy: &PluginPolicy,
+    ) -> anyhow::Result<WasmVfs> {
+        self.prepare_in(runtime, policy)?
+            .mount(&MountConfig::new("test:///"))
+    }
+
+    pub(crate) fn prepare_in(
+        &self,
+        runtime: &PluginRuntime,
+        policy: &PluginPolicy,
+    ) -> anyhow::Result<WasmPlugin> {
+        let component = Component::new(runtime.engine(), self.build())?;
+        runtime.prepare("test", &component, policy)
+    }
+}
+
+fn interface<'a>(
+    items: impl IntoIterator<Item = (&'a WorldKey, &'a WorldItem)>,
+) -> (&'a WorldKey, InterfaceId) {
+    items
+        .into_iter()
+        .find_map(|(key, item)| match item {
+            WorldItem::Interface { id, .. } => Some((key, *id)),
+            _ => None,
+        })
+        .unwrap()
+}
+
+fn val_type(ty: &WasmType) -> ValType {
+    match ty {
+        WasmType::I32 | WasmType::Pointer | WasmType::Length => ValType::I32,
+        WasmType::I64 | WasmType::PointerOrI64 => ValType::I64,
+        WasmType::F32 => ValType::F32,
+        WasmType::F64 => ValType::F64,
+    }
+}
+
+fn is_borrow_of(resolve: &Resolve, ty: &Type, resource: TypeId) -> bool {
+    let Type::Id(id) = ty else {
+        return false;
+    };
+    let TypeDefKind::Handle(Handle::Borrow(mut borrowed)) = resolve.types[*id].kind else {
+        return false;
+    };
+    // resources imported with `use` are aliases of the original type
+    while let TypeDefKind::Type(Type::Id(aliased)) = resolve.types[borrowed].kind {
+        borrowed = aliased;
+    }
+    borrowed == resource
+}
+
+fn body(borrowed: &[u32], body: Option<&Body>) -> Function {
+    let mut instructions = vec![];
+    for local in borrowed {
+        instructions.extend([
+            Instruction::LocalGet(*local),
+            Instruction::Call(RESOURCE_DROP_ABSOLUTE_PATH),
+        ]);
+    }
+    instructions.extend(match body.unwrap_or(&Body::Trap) {
+        Body::Trap => vec![Instruction::Unreachable],
+        Body::Loop => vec![
+            Instruction::Loop(wasm_encoder::BlockType::Empty),
+            Instruction::Br(0),
+            Instruction::End,
+            Instruction::Unreachable,
+        ],
+        Body::Code(instructions) => instructions.clone(),
+    });
+    let mut function = Function::new([]);
+    for instruction in instructions.iter().chain([&Instruction::End]) {
+        function.instruction(instruction);
+    }
+    function
+}
@@ -0,0 +1,100 @@
+use std::io;
+
+use crate::VfsError;
+
+impl From<io::Error> for VfsError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => VfsError::NotFound,
+            io::ErrorKind::PermissionDenied => VfsError::PermissionDenied,
+            io::ErrorKind::AlreadyExists => VfsError::AlreadyExists,
+            io::ErrorKind::NotADirectory => VfsError::NotADirectory,
+            io::ErrorKind::IsADirectory => VfsError::IsADirectory,
+            io::ErrorKind::DirectoryNotEmpty => VfsError::NotEmpty,
+            io::ErrorKind::Unsupported => VfsError::Unsupported,
+            io::ErrorKind::InvalidInput => VfsError::InvalidPath(error.to_string()),
+            _ => VfsError::Io(error.to_string()),
+        }
+    }
+}
+
+impl From<VfsError> for io::Error {
+    fn from(error: VfsError) -> Self {
+        let kind = match error {
+            VfsError::NotFound => io::ErrorKind::NotFound,
+            VfsError::PermissionDenied => io::ErrorKind::PermissionDenied,
+            VfsError::AlreadyExists => io::ErrorKind::AlreadyExists,
+            VfsError::NotADirectory => io::ErrorKind::NotADirectory,
+            VfsError::IsADirectory => io::ErrorKind::IsADirectory,
+            VfsError::NotEmpty => io::ErrorKind::DirectoryNotEmpty,
+            VfsError::InvalidPath(_) => io::ErrorKind::InvalidInput,
+            VfsError::Unsupported => io::ErrorKind::Unsupported,
+            VfsError::Io(_) | VfsError::PluginTrap(_) => io::ErrorKind::Other,
+            VfsError::Timeout => io::ErrorKind::TimedOut,
+            VfsError::LimitExceeded(_) => io::ErrorKind::OutOfMemory,
+            VfsError::InvalidResponse(_) => io::ErrorKind::InvalidData,
+            VfsError::Cancelled => io::ErrorKind::Interrupted,
+        };
+        io::Error::new(kind, error.message())
+    }
+}
+
+impl VfsError {
+    /// A human readable description of the error, suitable for showing to the user.
+    pub fn message(&self) -> String {
+        match self {
+            VfsError::NotFound => "no such file or folder".to_string(),
+            VfsError::PermissionDenied => "permission denied".to_string(),
+            VfsError::AlreadyExists => "the file or folder already exists".to_string(),
+            VfsError::NotADirectory => "not a folder".to_string(),
+            VfsError::IsADirectory => "is a folder".to_string(),
+            VfsError::NotEmpty => "the folder is not empty".to_string(),
+            VfsError::InvalidPath(reason) => format!("invalid path: {}", reason),
+            VfsError::Unsupported => "operation not supported".to_string(),
+            VfsError::Io(reason) => format!("i/o error: {}", reason),
+            VfsError::PluginTrap(reason) => format!("the plugin crashed: {}", reason),
+            VfsError::Timeout => "the plugin did not respond in time".to_string(),
+            VfsError::LimitExceeded(reason) => format!("the plugin was stopped: {}", reason),
+            VfsError::InvalidResponse(reason) => {
+                format!("the plugin returned invalid data: {}", reason)
+            }
+            VfsError::Cancelled => "cancelled".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use crate::VfsError;
+
+    #[test]
+    fn from_io_not_found() {
+        let error = io::Error::from(io::ErrorKind::NotFound);
+        assert_eq!(VfsError::from(error), VfsError::NotFound);
+    }
+
+    #[test]
+    fn from_io_permission_denied() {
+        let error = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert_eq!(VfsError::from(error), VfsError::PermissionDenied);
+    }
+
+    #[test]
+    fn from_io_other() {
+        let error = io::Error::other("disk on fire");
+        assert_eq!(
+            VfsError::from(error),
+            VfsError::Io("disk on fire".to_string())
+        );
+    }
+
+    #[test]
+    fn into_io() {
+        let error = io::Error::from(VfsError::NotFound);
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        let error = io::Error::from(VfsError::PluginTrap("unreachable".to_string()));
+        assert_eq!(error.kind(), io::ErrorKind::Other);
+    }
+}
@@ -1,5 +1,10 @@
 wasmtime::component::bindgen!({
-    async: false,
+    // calls into plugins are async, the host functions they import are not
+    // unless they wait for the user
+    async: {
+        only_imports: ["prompt-secret"],
+    },
+    trappable_imports: true,
     path: "wit/vfs.wit",
     additional_derives: [
         Eq,
@@ -12,9 +17,18 @@ wasmtime::component::bindgen!({
     }
 });
 
-pub use exports::junkyard_vfs::vfs_plugin::vfs::{Kind, Seek, Stat};
+pub use exports::junkyard_vfs::vfs_plugin::vfs::{
+    Capability, Datetime, DirEntry, Kind, MountConfig, PluginInfo, Seek, Stat, VfsError,
+};
+pub use junkyard_vfs::vfs_plugin::vfs_host::{LogLevel, StorageError};
 
+mod error;
+mod log;
+mod mount;
 mod path;
+mod plugin;
+mod stat;
 
 // local resources
-pub use path::AbsolutePath;
\ No newline at end of file
+pub use path::AbsolutePath;
+pub use plugin::{FileExports, FilesystemExports, VfsExports, VfsExportsPre, VERSION};
@@ -0,0 +1,26 @@
+use crate::MountConfig;
+
+impl MountConfig {
+    /// Mounts `uri` without any options.
+    pub fn new(uri: impl Into<String>) -> MountConfig {
+        MountConfig {
+            uri: uri.into(),
+            options: vec![],
+        }
+    }
+
+    pub fn with_option(mut self, key: impl Into<String>, value: impl Into<String>) -> MountConfig {
+        self.options.push((key.into(), value.into()));
+        self
+    }
+
+    /// The value of the option `key`, the last one if it was set more than
+    /// once.
+    pub fn option(&self, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
@@ -62,6 +62,10 @@ impl AbsolutePath {
         &self.path
     }
 
+    pub fn join(&self, name: impl AsRef<str>) -> AbsolutePath {
+        AbsolutePath::new(format!("{}/{}", self.path, name.as_ref()))
+    }
+
     pub fn parent(&self) -> AbsolutePath {
         if self.is_root() {
             self.clone()
@@ -359,6 +363,18 @@ mod tests {
             assert_eq!(path.parent().as_str(), "/folder/folder2");
         }
 
+        #[test]
+        fn join() {
+            let path = AbsolutePath::new("/folder");
+            assert_eq!(path.join("folder2").as_str(), "/folder/folder2");
+        }
+
+        #[test]
+        fn join_root() {
+            let path = AbsolutePath::new("/");
+            assert_eq!(path.join("folder").as_str(), "/folder");
+        }
+
         #[test]
         fn parent_folder_root() {
             let path = AbsolutePath::new("/");
@@ -0,0 +1,492 @@
+use std::collections::HashMap;
+
+use wasmtime::{
+    component::{
+        Component, ComponentExportIndex, ComponentNamedList, Func, InstancePre, Lift, Linker,
+        Lower, Resource, ResourceAny,
+    },
+    AsContextMut, Engine, Error, Result,
+};
+
+use crate::{
+    exports::junkyard_vfs::vfs_plugin::vfs::OpenFlags, AbsolutePath, DirEntry, MountConfig,
+    PluginInfo, Seek, Stat, VfsError,
+};
+
+/// The version of the `junkyard-vfs:vfs-plugin` package in `wit/vfs.wit`.
+pub const VERSION: &str = "1.0.0";
+
+const PACKAGE: &str = "junkyard-vfs:vfs-plugin";
+const VFS: &str = "junkyard-vfs:vfs-plugin/vfs";
+
+/// The functions of the `vfs` interface a plugin may lack, because it was
+/// built against an older minor version of the interface. No plugin works
+/// without `info` and `init`.
+const OPTIONAL: [&str; 14] = [
+    "[method]filesystem.read-dir",
+    "[method]filesystem.read-dir-with-stat",
+    "[method]filesystem.stat",
+    "[method]filesystem.lstat",
+    "[method]filesystem.read-link",
+    "[method]filesystem.create-symlink",
+    "[method]filesystem.open",
+    "[method]filesystem.unlink",
+    "[method]filesystem.create-dir",
+    "[method]filesystem.create-dir-all",
+    "[method]filesystem.rename",
+    "[method]file.read",
+    "[method]file.write",
+    "[method]file.seek",
+];
+
+/// A plugin linked to the host, ready to be instantiated.
+///
+/// Unlike the generated `VfsPluginPre`, the functions the plugin exports are
+/// looked up one by one, so that a plugin built against an older minor
+/// version of the interface loads, and calling a function it lacks fails
+/// with `unsupported`.
+pub struct VfsExportsPre<T> {
+    instance_pre: InstancePre<T>,
+    info: ComponentExportIndex,
+    init: ComponentExportIndex,
+    optional: Vec<(&'static str, ComponentExportIndex)>,
+}
+
+impl<T> Clone for VfsExportsPre<T> {
+    fn clone(&self) -> Self {
+        VfsExportsPre {
+            instance_pre: self.instance_pre.clone(),
+            info: self.info,
+            init: self.init,
+            optional: self.optional.clone(),
+        }
+    }
+}
+
+impl<T> VfsExportsPre<T> {
+    /// Resolves the imports of `component` with `linker`. A plugin built
+    /// against another major version of the interface is refused.
+    pub fn new(linker: &Linker<T>, component: &Component) -> Result<VfsExportsPre<T>> {
+        let version = plugin_version(component)?;
+        let instance_pre = linker.instantiate_pre(component).map_err(|error| {
+            if release(&version) > release(VERSION) {
+                error.context(format!(
+                    "the plugin needs version {} of {}, junkyard only provides version {}",
+                    version, PACKAGE, VERSION
+                ))
+            } else {
+                error
+            }
+        })?;
+        // the lookup finds the semver compatible version the plugin exports
+        let (_, vfs) = component
+            .export_index(None, &format!("{}@{}", VFS, VERSION))
+            .ok_or_else(|| Error::msg(format!("the plugin does not export {}", VFS)))?;
+        let function = |name: &str| {
+            component
+                .export_index(Some(&vfs), name)
+                .map(|(_, index)| index)
+        };
+        let required = |name: &str| {
+            function(name)
+                .ok_or_else(|| Error::msg(format!("the plugin does not export `{}`", name)))
+        };
+        Ok(VfsExportsPre {
+            info: required("info")?,
+            init: required("init")?,
+            optional: OPTIONAL
+                .into_iter()
+                .filter_map(|name| Some((name, function(name)?)))
+                .collect(),
+            instance_pre,
+        })
+    }
+
+    pub fn engine(&self) -> &Engine {
+        self.instance_pre.engine()
+    }
+
+    pub async fn instantiate_async(
+        &self,
+        mut store: impl AsContextMut<Data = T>,
+    ) -> Result<VfsExports>
+    where
+        T: Send,
+    {
+        let instance = self.instance_pre.instantiate_async(&mut store).await?;
+        let mut function = |name: &str, index: &ComponentExportIndex| {
+            instance
+                .get_func(&mut store, index)
+                .ok_or_else(|| Error::msg(format!("`{}` of the plugin is not a function", name)))
+        };
+        Ok(VfsExports {
+            info: function("info", &self.info)?,
+            init: function("init", &self.init)?,
+            optional: self
+                .optional
+                .iter()
+                .map(|(name, index)| Ok((*name, function(name, index)?)))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+/// The `vfs` interface of an instance of a plugin, with the same methods as
+/// the generated bindings.
+pub struct VfsExports {
+    info: Func,
+    init: Func,
+    optional: HashMap<&'static str, Func>,
+}
+
+impl VfsExports {
+    pub async fn call_info<S: AsContextMut>(&self, store: S) -> Result<PluginInfo>
+    where
+        S::Data: Send,
+    {
+        call(&self.info, store, ()).await
+    }
+
+    pub async fn call_init<S: AsContextMut>(
+        &self,
+        store: S,
+        config: &MountConfig,
+    ) -> Result<Result<ResourceAny, VfsError>>
+    where
+        S::Data: Send,
+    {
+        call(&self.init, store, (config,)).await
+    }
+
+    pub fn filesystem(&self) -> FilesystemExports<'_> {
+        FilesystemExports(self)
+    }
+
+    pub fn file(&self) -> FileExports<'_> {
+        FileExports(self)
+    }
+
+    async fn call_optional<S, P, R>(
+        &self,
+        name: &str,
+        store: S,
+        params: P,
+    ) -> Result<Result<R, VfsError>>
+    where
+        S: AsContextMut,
+        S::Data: Send,
+        P: ComponentNamedList + Lower + Send + Sync,
+        (Result<R, VfsError>,): ComponentNamedList + Lift + Send + Sync,
+    {
+        match self.optional.get(name) {
+            Some(func) => call(func, store, params).await,
+            None => Ok(Err(VfsError::Unsupported)),
+        }
+    }
+}
+
+/// The methods of the `filesystem` resource.
+pub struct FilesystemExports<'a>(&'a VfsExports);
+
+impl FilesystemExports<'_> {
+    pub async fn call_read_dir<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        path: Resource<AbsolutePath>,
+    ) -> Result<Result<Vec<Resource<AbsolutePath>>, VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional("[method]filesystem.read-dir", store, (filesystem, path))
+            .await
+    }
+
+    pub async fn call_read_dir_with_stat<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        path: Resource<AbsolutePath>,
+    ) -> Result<Result<Vec<DirEntry>, VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional(
+                "[method]filesystem.read-dir-with-stat",
+                store,
+                (filesystem, path),
+            )
+            .await
+    }
+
+    pub async fn call_stat<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        path: Resource<AbsolutePath>,
+    ) -> Result<Result<Stat, VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional("[method]filesystem.stat", store, (filesystem, path))
+            .await
+    }
+
+    pub async fn call_lstat<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        path: Resource<AbsolutePath>,
+    ) -> Result<Result<Stat, VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional("[method]filesystem.lstat", store, (filesystem, path))
+            .await
+    }
+
+    pub async fn call_read_link<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        path: Resource<AbsolutePath>,
+    ) -> Result<Result<String, VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional("[method]filesystem.read-link", store, (filesystem, path))
+            .await
+    }
+
+    pub async fn call_create_symlink<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        target: &str,
+        link: Resource<AbsolutePath>,
+    ) -> Result<Result<(), VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional(
+                "[method]filesystem.create-symlink",
+                store,
+                (filesystem, target, link),
+            )
+            .await
+    }
+
+    pub async fn call_open<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        path: Resource<AbsolutePath>,
+        options: OpenFlags,
+    ) -> Result<Result<ResourceAny, VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional(
+                "[method]filesystem.open",
+                store,
+                (filesystem, path, options),
+            )
+            .await
+    }
+
+    pub async fn call_unlink<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        path: Resource<AbsolutePath>,
+    ) -> Result<Result<(), VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional("[method]filesystem.unlink", store, (filesystem, path))
+            .await
+    }
+
+    pub async fn call_create_dir<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        path: Resource<AbsolutePath>,
+    ) -> Result<Result<(), VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional("[method]filesystem.create-dir", store, (filesystem, path))
+            .await
+    }
+
+    pub async fn call_create_dir_all<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        path: Resource<AbsolutePath>,
+    ) -> Result<Result<(), VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional(
+                "[method]filesystem.create-dir-all",
+                store,
+                (filesystem, path),
+            )
+            .await
+    }
+
+    pub async fn call_rename<S: AsContextMut>(
+        &self,
+        store: S,
+        filesystem: ResourceAny,
+        from_path: Resource<AbsolutePath>,
+        to_path: Resource<AbsolutePath>,
+    ) -> Result<Result<(), VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional(
+                "[method]filesystem.rename",
+                store,
+                (filesystem, from_path, to_path),
+            )
+            .await
+    }
+}
+
+/// The methods of the `file` resource.
+pub struct FileExports<'a>(&'a VfsExports);
+
+impl FileExports<'_> {
+    pub async fn call_read<S: AsContextMut>(
+        &self,
+        store: S,
+        file: ResourceAny,
+        len: u64,
+    ) -> Result<Result<Vec<u8>, VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional("[method]file.read", store, (file, len))
+            .await
+    }
+
+    pub async fn call_write<S: AsContextMut>(
+        &self,
+        store: S,
+        file: ResourceAny,
+        data: &[u8],
+    ) -> Result<Result<u64, VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional("[method]file.write", store, (file, data))
+            .await
+    }
+
+    pub async fn call_seek<S: AsContextMut>(
+        &self,
+        store: S,
+        file: ResourceAny,
+        seek: Seek,
+    ) -> Result<Result<u64, VfsError>>
+    where
+        S::Data: Send,
+    {
+        self.0
+            .call_optional("[method]file.seek", store, (file, seek))
+            .await
+    }
+}
+
+/// Calls `func`, whose type is checked against `P` and `R` on every call.
+async fn call<S, P, R>(func: &Func, mut store: S, params: P) -> Result<R>
+where
+    S: AsContextMut,
+    S::Data: Send,
+    P: ComponentNamedList + Lower + Send + Sync,
+    (R,): ComponentNamedList + Lift + Send + Sync,
+{
+    let func = func.typed::<P, (R,)>(&store)?;
+    let (ret,) = func.call_async(&mut store, params).await?;
+    func.post_return_async(&mut store).await?;
+    Ok(ret)
+}
+
+/// The version of the interface `component` was built against, which has
+/// to be compatible with [`VERSION`].
+fn plugin_version(component: &Component) -> Result<String> {
+    let name = component
+        .component_type()
+        .exports(component.engine())
+        .map(|(name, _)| name.to_string())
+        .find(|name| name.split('@').next() == Some(VFS))
+        .ok_or_else(|| Error::msg(format!("the plugin does not export {}", VFS)))?;
+    let Some((_, version)) = name.split_once('@') else {
+        return Err(Error::msg(format!(
+            "the plugin was built against an unversioned {}, rebuild it against version {}",
+            PACKAGE, VERSION
+        )));
+    };
+    if !compatible(version, VERSION) {
+        return Err(Error::msg(format!(
+            "the plugin was built against version {} of {}, which is not compatible with \
+             version {} that junkyard provides",
+            version, PACKAGE, VERSION
+        )));
+    }
+    Ok(version.to_string())
+}
+
+/// The major and minor numbers of a semver version.
+fn release(version: &str) -> (u64, u64) {
+    let mut numbers = version
+        .split(['.', '-', '+'])
+        .map(|number| number.parse().unwrap_or(0));
+    (numbers.next().unwrap_or(0), numbers.next().unwrap_or(0))
+}
+
+/// Whether an interface at `version` is a subset or a superset of the one at
+/// `other`, with the semver rules of the component model: versions with
+/// the same major number are, and below 1.0 the same minor number as well.
+fn compatible(version: &str, other: &str) -> bool {
+    let (major, minor) = release(version);
+    let (other_major, other_minor) = release(other);
+    major == other_major && (major != 0 || minor == other_minor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compatible, release, VERSION};
+
+    #[test]
+    fn versions() {
+        assert_eq!(release("1.2.3"), (1, 2));
+        assert_eq!(release("0.3.0-rc.1"), (0, 3));
+        assert!(compatible("1.0.0", "1.4.2"));
+        assert!(!compatible("2.0.0", "1.4.2"));
+        assert!(compatible("0.2.0", "0.2.7"));
+        assert!(!compatible("0.1.0", "0.2.0"));
+    }
+
+    #[test]
+    fn version_of_the_wit() {
+        let wit = include_str!("../wit/vfs.wit");
+        assert!(wit.starts_with(&format!("package junkyard-vfs:vfs-plugin@{};", VERSION)));
+    }
+}
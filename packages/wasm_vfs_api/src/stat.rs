@@ -0,0 +1,86 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{Datetime, Kind, Stat};
+
+impl Stat {
+    /// Builds a [`Stat`] that only carries the kind and the size, all the
+    /// other metadata is left empty.
+    pub fn new(kind: Kind, size: u64) -> Stat {
+        Stat {
+            kind,
+            size,
+            modified: None,
+            accessed: None,
+            created: None,
+            mode: None,
+            uid: None,
+            gid: None,
+            user: None,
+            group: None,
+            inode: None,
+            device: None,
+            nlink: None,
+        }
+    }
+}
+
+impl From<SystemTime> for Datetime {
+    fn from(value: SystemTime) -> Self {
+        match value.duration_since(UNIX_EPOCH) {
+            Ok(duration) => Datetime {
+                seconds: duration.as_secs() as i64,
+                nanoseconds: duration.subsec_nanos(),
+            },
+            Err(before_epoch) => {
+                let duration = before_epoch.duration();
+                if duration.subsec_nanos() == 0 {
+                    Datetime {
+                        seconds: -(duration.as_secs() as i64),
+                        nanoseconds: 0,
+                    }
+                } else {
+                    Datetime {
+                        seconds: -(duration.as_secs() as i64) - 1,
+                        nanoseconds: 1_000_000_000 - duration.subsec_nanos(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl From<Datetime> for SystemTime {
+    fn from(value: Datetime) -> Self {
+        let nanoseconds = Duration::from_nanos(value.nanoseconds as u64);
+        if value.seconds >= 0 {
+            UNIX_EPOCH + Duration::from_secs(value.seconds as u64) + nanoseconds
+        } else {
+            UNIX_EPOCH - Duration::from_secs(value.seconds.unsigned_abs()) + nanoseconds
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use crate::Datetime;
+
+    #[test]
+    fn datetime_after_epoch() {
+        let time = UNIX_EPOCH + Duration::new(1_700_000_000, 500);
+        let datetime = Datetime::from(time);
+        assert_eq!(datetime.seconds, 1_700_000_000);
+        assert_eq!(datetime.nanoseconds, 500);
+        assert_eq!(SystemTime::from(datetime), time);
+    }
+
+    #[test]
+    fn datetime_before_epoch() {
+        let time = UNIX_EPOCH - Duration::new(10, 250);
+        let datetime = Datetime::from(time);
+        assert_eq!(datetime.seconds, -11);
+        assert_eq!(datetime.nanoseconds, 999_999_750);
+        assert_eq!(SystemTime::from(datetime), time);
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use chrono::{DateTime, Local};
+use cursive::{
+    view::{Nameable, Resizable, ScrollStrategy, Scrollable},
+    views::{Dialog, TextView},
+    Cursive,
+};
+use wasm::{LogRecord, PluginLog};
+
+const LAYER: &str = "log-layer";
+const TEXT: &str = "log";
+
+fn format_record(record: &LogRecord) -> String {
+    format!(
+        "{} {:<5} {}: {}",
+        DateTime::<Local>::from(record.time).format("%H:%M:%S"),
+        record.level,
+        record.plugin,
+        record.message
+    )
+}
+
+fn format_log(log: &PluginLog) -> String {
+    log.records()
+        .iter()
+        .map(format_record)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Shows what plugins logged over the panels, or hides it if it is shown.
+pub fn toggle_log(siv: &mut Cursive, log: Arc<PluginLog>) {
+    let screen = siv.screen_mut();
+    if let Some(position) = screen.find_layer_from_name(LAYER) {
+        screen.remove_layer(position);
+        return;
+    }
+    let text = TextView::new(format_log(&log))
+        .with_name(TEXT)
+        .scrollable()
+        .scroll_strategy(ScrollStrategy::StickToBottom);
+    let dialog = Dialog::around(text)
+        .title("Plugin log")
+        .button("Clear", move |siv| {
+            log.clear();
+            siv.call_on_name(TEXT, |text: &mut TextView| text.set_content(""));
+        })
+        .button("Close", |siv| {
+            siv.pop_layer();
+        })
+        .with_name(LAYER)
+        .full_screen();
+    siv.add_layer(dialog);
+}
+
+/// Keeps the log view up to date while it is shown, `seen` is how many
+/// records it has shown so far.
+pub fn refresh_log(siv: &mut Cursive, log: &PluginLog, seen: &AtomicU64) {
+    let written = log.written();
+    if seen.swap(written, Ordering::Relaxed) == written {
+        return;
+    }
+    siv.call_on_name(TEXT, |text: &mut TextView| {
+        text.set_content(format_log(log))
+    });
+}
@@ -1,36 +1,78 @@
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc,
+use std::{
+    env, process,
+    sync::{atomic::AtomicU64, Arc},
+    time::Duration,
 };
 
 use cursive::{
-    menu,
+    event::Event,
     theme::BorderStyle,
-    view::{Nameable, Resizable},
-    views::{Dialog, FixedLayout, OnLayoutView, Panel},
-    Rect, View, With,
+    view::Nameable,
+    view::Resizable,
+    views::{Dialog, EditView, FixedLayout, LinearLayout, OnLayoutView, SelectView, TextView},
+    CbSink, Cursive, Rect, View,
 };
 use local_fs::LocalFileSystem;
-use panel::init_panel;
-use vfs::AbsolutePath;
-use wasm::load_wasm_vfs;
+use log_view::{refresh_log, toggle_log};
+use panel::{init_panel, open_in_panel, refresh_plugin_panels};
+use secret_prompt::DialogPrompt;
+use tokio::{
+    runtime::{Handle, Runtime},
+    time,
+};
+use vfs::{AbsolutePath, AsyncVfs, ThreadedVfs};
+use wasm::{ComponentCache, MountConfig, PluginRegistry, PluginRuntime, RegisteredPlugin};
 
+mod log_view;
 mod panel;
+mod progress;
+mod secret_prompt;
+
+/// How often the components of loaded plugins are checked for changes.
+const WATCH_PLUGINS: Duration = Duration::from_secs(1);
 
 fn main() {
+    if env::args().any(|arg| arg == "--clear-plugin-cache") {
+        let Some(cache) = ComponentCache::in_home() else {
+            eprintln!("there is no home folder to keep a plugin cache in");
+            process::exit(1);
+        };
+        if let Err(error) = cache.clear() {
+            eprintln!("failed to clear the plugin cache: {}", error);
+            process::exit(1);
+        }
+        println!("the plugin cache was cleared");
+        return;
+    }
+
     let mut siv = cursive::default();
 
     siv.load_toml(include_str!("../visual.toml")).unwrap();
 
     siv.set_autorefresh(true);
 
-    // let provider_left = Arc::new(LocalFileSystem {});
-    let provider_left = Arc::new(load_wasm_vfs(&AbsolutePath::new("/Users/alexandru/programe/Wylio/junkyard/packages/filesystem/wasm_local_js_fs/component.wasm"), &AbsolutePath::new("/")).unwrap());
-    // let provider_right = Arc::new(LocalFileSystem {});
-    let provider_right = Arc::new(load_wasm_vfs(&AbsolutePath::new("/Users/alexandru/programe/Wylio/junkyard/target/wasm32-wasip2/release/wasm_local_fs.wasm"), &AbsolutePath::new("/")).unwrap());
+    // panels start on the local filesystem, plugins are opened with 'p'
+    let registry = Arc::new(PluginRegistry::discover());
+    let provider: Arc<dyn AsyncVfs> = Arc::new(ThreadedVfs::new(LocalFileSystem {}));
 
-    let left = init_panel("left", provider_left, AbsolutePath::from("/"));
-    let right = init_panel("right", provider_right, AbsolutePath::from("/"));
+    // plugins run on the runtime so that slow ones do not freeze the interface
+    let runtime = Runtime::new().unwrap();
+    let left = init_panel(
+        &mut siv,
+        "left",
+        "Local",
+        provider.clone(),
+        AbsolutePath::from("/"),
+        runtime.handle().clone(),
+    );
+    let right = init_panel(
+        &mut siv,
+        "right",
+        "Local",
+        provider,
+        AbsolutePath::from("/"),
+        runtime.handle().clone(),
+    );
 
     let mut no_shadow_theme = siv.current_theme().clone();
     no_shadow_theme.shadow = false; // Disable shadow
@@ -41,14 +83,8 @@ fn main() {
     siv.add_fullscreen_layer(
         OnLayoutView::new(
             FixedLayout::new()
-                .child(
-                    Rect::from_size((0, 0), (0, 0)),
-                    Panel::new(left.with_name("left").min_size((30, 20))).title("Left"),
-                )
-                .child(
-                    Rect::from_size((0, 0), (0, 0)),
-                    Panel::new(right.with_name("right").min_size((30, 20))).title("Right"),
-                ),
+                .child(Rect::from_size((0, 0), (0, 0)), left)
+                .child(Rect::from_size((0, 0), (0, 0)), right),
             |layout, size| {
                 if size.x > 1 && size.y > 2 {
                     layout.set_child_position(0, Rect::from_size((0, 0), (size.x / 2, size.y - 2)));
@@ -133,6 +169,156 @@ fn main() {
     // siv.set_autohide_menu(false);
 
     siv.add_global_callback('q', |s| s.quit());
+    let handle = runtime.handle().clone();
+    let plugins = registry.clone();
+    siv.add_global_callback('p', move |siv| choose_plugin(siv, &plugins, &handle));
+    if let Ok(plugin_runtime) = PluginRuntime::global() {
+        // plugins ask for passwords in a dialog
+        plugin_runtime.set_secret_prompt(Arc::new(DialogPrompt::new(siv.cb_sink().clone())));
+        // what plugins log and write to their output is shown with 'l'
+        let log = plugin_runtime.log().clone();
+        siv.add_global_callback('l', move |siv| toggle_log(siv, log.clone()));
+        let log = plugin_runtime.log().clone();
+        let seen = AtomicU64::new(0);
+        siv.add_global_callback(Event::Refresh, move |siv| refresh_log(siv, &log, &seen));
+    }
+
+    watch_plugins(siv.cb_sink().clone(), registry.clone(), runtime.handle());
+
+    for error in registry.errors() {
+        siv.add_layer(Dialog::info(error));
+    }
 
     siv.run();
 }
+
+/// Lets the user pick one of the registered plugins and the panel to open it
+/// in.
+fn choose_plugin(siv: &mut Cursive, registry: &PluginRegistry, runtime: &Handle) {
+    if registry.plugins().is_empty() {
+        siv.add_layer(Dialog::info(
+            "No plugins are installed in ~/.junkyard/plugins",
+        ));
+        return;
+    }
+    let mut plugins = SelectView::new();
+    for plugin in registry.plugins() {
+        let manifest = &plugin.manifest;
+        plugins.add_item(
+            format!(
+                "{} {} ({}://)",
+                manifest.name, manifest.version, manifest.scheme
+            ),
+            plugin.clone(),
+        );
+    }
+    // every plugin suggests what to mount, the user can mount anything else
+    plugins.set_on_select(|siv, plugin: &Arc<RegisteredPlugin>| {
+        let uri = plugin.default_uri();
+        siv.call_on_name("uri", |edit: &mut EditView| edit.set_content(uri));
+    });
+    let uri = EditView::new().content(registry.plugins()[0].default_uri());
+    let mut dialog = Dialog::around(
+        LinearLayout::vertical()
+            .child(plugins.with_name("plugins"))
+            .child(TextView::new("Mount"))
+            .child(uri.with_name("uri")),
+    )
+    .title("Open plugin");
+    for (label, panel) in [("Left", "left"), ("Right", "right")] {
+        let runtime = runtime.clone();
+        dialog.add_button(label, move |siv| {
+            let plugin = siv
+                .call_on_name(
+                    "plugins",
+                    |plugins: &mut SelectView<Arc<RegisteredPlugin>>| plugins.selection(),
+                )
+                .flatten();
+            let uri = siv
+                .call_on_name("uri", |edit: &mut EditView| edit.get_content())
+                .map(|uri| uri.to_string())
+                .unwrap_or_default();
+            siv.pop_layer();
+            if let Some(plugin) = plugin {
+                open_plugin(siv, panel, (*plugin).clone(), uri, &runtime);
+            }
+        });
+    }
+    dialog.add_button("Cancel", |siv| {
+        siv.pop_layer();
+    });
+    siv.add_layer(dialog);
+}
+
+/// Plugins are compiled the first time they are opened and every panel
+/// mounts a filesystem of its own, both are done on the runtime.
+fn open_plugin(
+    siv: &mut Cursive,
+    panel: &'static str,
+    plugin: Arc<RegisteredPlugin>,
+    uri: String,
+    runtime: &Handle,
+) {
+    let sink = siv.cb_sink().clone();
+    runtime.spawn_blocking(move || {
+        let mounted = plugin
+            .load()
+            .and_then(|loaded| loaded.mount(&MountConfig::new(&uri)));
+        let _ = sink.send(Box::new(move |siv| match mounted {
+            Ok(vfs) => {
+                // what the plugin says about itself, if it says anything
+                let info = vfs.info();
+                let title = match info.name.is_empty() {
+                    true => format!("{} - {}", plugin.manifest.name, uri),
+                    false => format!("{} {} - {}", info.name, info.version, uri),
+                };
+                open_in_panel(
+                    siv,
+                    panel,
+                    title,
+                    Arc::new(vfs),
+                    Some(plugin.manifest.name.clone()),
+                    plugin.root(),
+                )
+            }
+            Err(error) => siv.add_layer(Dialog::info(format!(
+                "Failed to mount {} on {}: {:#}",
+                plugin.manifest.name, uri, error
+            ))),
+        }));
+    });
+}
+
+/// Reloads the plugins whose component changed, the panels showing them
+/// load their folder again with the new version. A version that fails to
+/// load is reported and the previous one keeps running.
+fn watch_plugins(sink: CbSink, registry: Arc<PluginRegistry>, runtime: &Handle) {
+    runtime.spawn(async move {
+        loop {
+            time::sleep(WATCH_PLUGINS).await;
+            let registry = registry.clone();
+            let Ok(reloaded) = tokio::task::spawn_blocking(move || registry.reload_changed()).await
+            else {
+                continue;
+            };
+            if reloaded.is_empty() {
+                continue;
+            }
+            let sent = sink.send(Box::new(move |siv| {
+                for (name, result) in reloaded {
+                    match result {
+                        Ok(()) => refresh_plugin_panels(siv, &name),
+                        Err(error) => siv.add_layer(Dialog::info(format!(
+                            "Failed to reload {}: {:#}, the previous version keeps running",
+                            name, error
+                        ))),
+                    }
+                }
+            }));
+            // the interface is gone
+            if sent.is_err() {
+                return;
+            }
+        }
+    });
+}
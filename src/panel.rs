@@ -1,15 +1,27 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering as AtomicOrdering};
+use std::sync::Mutex;
+use std::time::SystemTime;
 use std::{cmp::Ordering, sync::Arc};
 
+use chrono::{DateTime, Local};
 use cursive::align::HAlign;
-use cursive::views::Dialog;
+use cursive::view::{Nameable, Resizable};
+use cursive::views::{Dialog, NamedView, OnEventView, Panel, ResizedView, TextView};
+use cursive::{CbSink, Cursive};
 use cursive_table_view::{TableView, TableViewItem};
-use vfs::{AbsolutePath, Kind, Vfs, VfsResult};
+use tokio::runtime::Handle;
+use vfs::{AbsolutePath, AsyncVfs, Datetime, Kind, Operation, Stat, VfsError, VfsResult};
+
+use crate::progress::show_progress;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub enum Data {
     Name,
     Kind,
     Len,
+    Modified,
+    Mode,
 }
 
 impl AsRef<str> for Data {
@@ -18,6 +30,28 @@ impl AsRef<str> for Data {
             Data::Name => "Name",
             Data::Kind => "Kind",
             Data::Len => "Len",
+            Data::Modified => "Modified",
+            Data::Mode => "Mode",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Link {
+    target: String,
+    /// the kind of the file the link points to, `Kind::Unknown` for broken links
+    target_kind: Kind,
+}
+
+impl Link {
+    async fn resolve(provider: &dyn AsyncVfs, path: &AbsolutePath) -> Link {
+        Link {
+            target: provider.read_link(path).await.unwrap_or_default(),
+            target_kind: provider
+                .stat(path)
+                .await
+                .map(|stat| stat.kind)
+                .unwrap_or(Kind::Unknown),
         }
     }
 }
@@ -26,32 +60,138 @@ impl AsRef<str> for Data {
 pub struct File {
     name: String,
     full_path: AbsolutePath,
-    kind: Kind,
-    len: u64,
+    stat: Stat,
+    link: Option<Link>,
 }
 
 impl File {
-    pub fn new(name: String, full_path: AbsolutePath, kind: Kind, len: u64) -> File {
+    pub fn new(name: String, full_path: AbsolutePath, stat: Stat) -> File {
         File {
             name,
             full_path,
-            kind,
-            len,
+            stat,
+            link: None,
+        }
+    }
+
+    fn parent_folder(full_path: AbsolutePath) -> File {
+        File::new("..".to_string(), full_path, Stat::new(Kind::Folder, 0))
+    }
+
+    /// Folders and links to folders can both be entered.
+    fn is_folder(&self) -> bool {
+        self.stat.kind == Kind::Folder
+            || self
+                .link
+                .as_ref()
+                .is_some_and(|link| link.target_kind == Kind::Folder)
+    }
+}
+
+fn format_datetime(datetime: Option<Datetime>) -> String {
+    datetime
+        .map(|datetime| {
+            DateTime::<Local>::from(SystemTime::from(datetime))
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+fn format_mode(mode: Option<u32>) -> String {
+    mode.map(|mode| {
+        let mut permissions = String::with_capacity(9);
+        for shift in [6, 3, 0] {
+            let bits = (mode >> shift) & 0o7;
+            permissions.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+            permissions.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+            permissions.push(if bits & 0o1 != 0 { 'x' } else { '-' });
         }
+        permissions
+    })
+    .unwrap_or_default()
+}
+
+fn format_owner(name: &Option<String>, id: Option<u32>) -> String {
+    match (name, id) {
+        (Some(name), Some(id)) => format!("{} ({})", name, id),
+        (Some(name), None) => name.clone(),
+        (None, Some(id)) => id.to_string(),
+        (None, None) => "N/A".to_string(),
     }
 }
 
+fn format_optional<T: ToString>(value: Option<T>) -> String {
+    value
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+fn show_details(siv: &mut Cursive, file: &File) {
+    let stat = &file.stat;
+    let or_na = |value: String| {
+        if value.is_empty() {
+            "N/A".to_string()
+        } else {
+            value
+        }
+    };
+    let details = [
+        ("Path", file.full_path.as_str().to_string()),
+        (
+            "Target",
+            file.link
+                .as_ref()
+                .map(|link| link.target.clone())
+                .unwrap_or_else(|| "N/A".to_string()),
+        ),
+        ("Kind", file.to_column(Data::Kind)),
+        ("Size", stat.size.to_string()),
+        ("Modified", or_na(format_datetime(stat.modified))),
+        ("Accessed", or_na(format_datetime(stat.accessed))),
+        ("Created", or_na(format_datetime(stat.created))),
+        (
+            "Mode",
+            or_na(
+                stat.mode
+                    .map(|mode| format!("{:04o} {}", mode & 0o7777, format_mode(Some(mode))))
+                    .unwrap_or_default(),
+            ),
+        ),
+        ("Owner", format_owner(&stat.user, stat.uid)),
+        ("Group", format_owner(&stat.group, stat.gid)),
+        ("Inode", format_optional(stat.inode)),
+        ("Device", format_optional(stat.device)),
+        ("Links", format_optional(stat.nlink)),
+    ]
+    .iter()
+    .map(|(label, value)| format!("{:>9}: {}", label, value))
+    .collect::<Vec<_>>()
+    .join("\n");
+    siv.add_layer(
+        Dialog::around(TextView::new(details))
+            .title(file.name.clone())
+            .dismiss_button("Close"),
+    );
+}
+
 impl TableViewItem<Data> for File {
     fn to_column(&self, column: Data) -> String {
         match column {
-            Data::Name => self.name.clone(),
-            Data::Kind => format!("{}", match self.kind {
-                                    Kind::File => "file",
-                                    Kind::Folder => "<dir>",
-                                    Kind::Link => "link",
-                                    Kind::Unknown => "N/A",
-                                }),
-            Data::Len => format!("{}", self.len),
+            Data::Name => match &self.link {
+                Some(link) => format!("{} -> {}", self.name, link.target),
+                None => self.name.clone(),
+            },
+            Data::Kind => match self.stat.kind {
+                Kind::File => "file",
+                Kind::Folder => "<dir>",
+                Kind::Link => "link",
+                Kind::Unknown => "N/A",
+            }
+            .to_string(),
+            Data::Len => format!("{}", self.stat.size),
+            Data::Modified => format_datetime(self.stat.modified),
+            Data::Mode => format_mode(self.stat.mode),
         }
     }
 
@@ -61,118 +201,274 @@ impl TableViewItem<Data> for File {
     {
         match column {
             Data::Name => self.name.cmp(&other.name),
-            Data::Kind => if self.name == ".." {
-                            Ordering::Less
-                        } else if matches!(self.kind, Kind::Folder) && !matches!(other.kind, Kind::Folder) {
+            Data::Kind => if self.name == ".." || (self.is_folder() && !other.is_folder()) {
                             Ordering::Less
-                        } else if !matches!(self.kind, Kind::Folder) && matches!(other.kind, Kind::Folder) {
+                        } else if !self.is_folder() && other.is_folder() {
                             Ordering::Greater
                         } else {
                             match column {
                                 Data::Name => self.name.cmp(&other.name),
-                                Data::Kind => self.kind.cmp(&other.kind),
-                                Data::Len => self.len.cmp(&other.len),
+                                Data::Kind => self.stat.kind.cmp(&other.stat.kind),
+                                Data::Len => self.stat.size.cmp(&other.stat.size),
+                                Data::Modified => self.stat.modified.cmp(&other.stat.modified),
+                                Data::Mode => self.stat.mode.cmp(&other.stat.mode),
                             }
                         },
-            Data::Len => self.len.cmp(&other.len),
+            Data::Len => self.stat.size.cmp(&other.stat.size),
+            Data::Modified => self.stat.modified.cmp(&other.stat.modified),
+            Data::Mode => self.stat.mode.cmp(&other.stat.mode),
         }
     }
 }
 
-fn list_files(provider: &dyn Vfs, path: &AbsolutePath) -> VfsResult<Vec<File>> {
-    // let start_time = std::time::Instant::now();
-    let entries = provider.read_dir(&path)?;
-    // let entries_time = std::time::Instant::now();
-    let mut files = entries
-        .into_iter()
-        .map(|entry| {
-            let (kind, size) = if let Ok(stat) = provider.stat(&entry) {
-                (stat.kind, stat.size)
-            } else {
-                (Kind::Unknown, 0)
-            };
-            File::new(entry.name().into(), entry, kind, size)
-        })
-        .collect::<Vec<_>>();
+async fn list_files(provider: &dyn AsyncVfs, path: &AbsolutePath) -> VfsResult<Vec<File>> {
+    let entries = provider.read_dir_with_stat(path).await?;
+    let mut files = Vec::with_capacity(entries.len() + 1);
     if !path.is_root() {
-        files.insert(
-            0,
-            File::new("..".to_string(), path.clone(), Kind::Folder, 0),
-        );
+        files.push(File::parent_folder(path.clone()));
+    }
+    for entry in entries {
+        let full_path = path.join(&entry.name);
+        let link = match entry.stat.kind {
+            Kind::Link => Some(Link::resolve(provider, &full_path).await),
+            _ => None,
+        };
+        files.push(File {
+            link,
+            ..File::new(entry.name, full_path, entry.stat)
+        });
     }
-    // let list_time = std::time::Instant::now();
-    // eprintln!("{}: entries {:?}, list {:?}", path.path(), entries_time-start_time, list_time-entries_time);
     Ok(files)
 }
 
+/// Where a panel loads its folders, loads run on the runtime and their
+/// results are sent back to the interface when they are done.
+#[derive(Clone)]
+struct Loader {
+    table_id: String,
+    provider: Arc<dyn AsyncVfs>,
+    runtime: Handle,
+    sink: CbSink,
+    /// the last load that was started, older ones are dropped when they end
+    latest: Arc<AtomicU64>,
+    /// the folder the panel shows
+    current: Arc<Mutex<AbsolutePath>>,
+    /// the plugin the panel shows, if it shows one
+    plugin: Option<String>,
+}
+
+impl Loader {
+    /// Loads `path` into the table, selecting the entry named `select`.
+    /// `fallback` is shown instead if the folder cannot be read.
+    fn load(&self, path: AbsolutePath, select: Option<String>, fallback: File) {
+        let load = self.latest.fetch_add(1, AtomicOrdering::SeqCst) + 1;
+        // slow filesystems show how far they got and can be cancelled
+        let operation = Arc::new(Operation::new());
+        let done = Arc::new(AtomicBool::new(false));
+        let provider = self
+            .provider
+            .with_operation(operation.clone())
+            .unwrap_or_else(|| self.provider.clone());
+        show_progress(
+            &self.runtime,
+            self.sink.clone(),
+            format!("{}-progress", self.table_id),
+            format!("Loading {}", path.as_str()),
+            operation,
+            done.clone(),
+        );
+        let loader = self.clone();
+        self.runtime.spawn(async move {
+            let items = list_files(provider.as_ref(), &path).await;
+            done.store(true, AtomicOrdering::SeqCst);
+            let notices = loader.provider.take_notices();
+            let latest = loader.latest.clone();
+            let current = loader.current.clone();
+            let table_id = loader.table_id.clone();
+            let _ = loader.sink.send(Box::new(move |siv| {
+                for notice in notices {
+                    siv.add_layer(Dialog::info(notice.message()));
+                }
+                // a cancelled load leaves the panel as it was
+                if latest.load(AtomicOrdering::SeqCst) != load
+                    || matches!(items, Err(VfsError::Cancelled))
+                {
+                    return;
+                }
+                let items = items.unwrap_or_else(|err| {
+                    siv.add_layer(Dialog::info(format!(
+                        "Failed to read folder contents: {}",
+                        err.message()
+                    )));
+                    vec![fallback]
+                });
+                *current.lock().unwrap() = path;
+                siv.call_on_name(&table_id, |table: &mut TableView<File, Data>| {
+                    let selected_index =
+                        select.and_then(|select| items.iter().position(|f| f.name == select));
+                    table.set_items(items);
+                    table.set_selected_row(0);
+                    if let Some(item_index) = selected_index {
+                        table.set_selected_item(item_index);
+                    }
+                });
+            }));
+        });
+    }
+}
+
+/// The loaders of all panels, kept in the user data of the `Cursive` root
+/// so that the provider of a panel can be replaced.
+#[derive(Default)]
+struct Panels(HashMap<String, Loader>);
+
+fn panel_loader(siv: &mut Cursive, id: &str) -> Option<Loader> {
+    siv.user_data::<Panels>()?.0.get(id).cloned()
+}
+
+/// The frame around a panel, it shows what the panel is browsing.
+type Frame = Panel<ResizedView<OnEventView<NamedView<TableView<File, Data>>>>>;
+
+fn frame_id(id: &str) -> String {
+    format!("{}-frame", id)
+}
+
 pub fn init_panel(
+    siv: &mut Cursive,
     id: impl AsRef<str>,
-    provider: Arc<dyn Vfs>,
+    title: impl Into<String>,
+    provider: Arc<dyn AsyncVfs>,
     path: AbsolutePath,
-) -> TableView<File, Data> {
+    runtime: Handle,
+) -> NamedView<Frame> {
     let mut table = TableView::<File, Data>::new()
-        .column(Data::Name, Data::Name.as_ref(), |c| c.width_percent(60))
+        .column(Data::Name, Data::Name.as_ref(), |c| c.width_percent(40))
         .column(Data::Kind, Data::Kind.as_ref(), |c| c.align(HAlign::Center))
         .column(Data::Len, Data::Len.as_ref(), |c| {
             c.ordering(Ordering::Greater)
                 .align(HAlign::Right)
-                .width_percent(20)
-        });
-    let items = list_files(provider.as_ref(), &path).unwrap_or(vec![File::new(
-        "..".to_string(),
-        path.clone(),
-        Kind::Folder,
-        0,
-    )]);
+                .width_percent(15)
+        })
+        .column(Data::Modified, Data::Modified.as_ref(), |c| c.width(16))
+        .column(Data::Mode, Data::Mode.as_ref(), |c| c.width(9));
+    let loader = Loader {
+        table_id: id.as_ref().to_string(),
+        provider,
+        runtime,
+        sink: siv.cb_sink().clone(),
+        latest: Arc::new(AtomicU64::new(0)),
+        current: Arc::new(Mutex::new(path.clone())),
+        plugin: None,
+    };
+    // the folder shows up when it is loaded, the interface does not wait
+    loader.load(path.clone(), None, File::parent_folder(path));
+    if siv.user_data::<Panels>().is_none() {
+        siv.set_user_data(Panels::default());
+    }
+    if let Some(panels) = siv.user_data::<Panels>() {
+        panels.0.insert(id.as_ref().to_string(), loader);
+    }
     let table_id = id.as_ref().to_string();
-    let vfs = provider.clone();
     table.set_on_submit(move |siv, _row, index| {
-        let sink = siv.cb_sink().clone();
-        siv.call_on_name(&table_id, |table: &mut TableView<File, Data>| {
-            let file = table.borrow_item(index).unwrap();
-            match file.kind {
-                Kind::Folder => {
-                    // let path = PathBuf::from(&file.full_path);
-                    let next_path = if file.name == ".." {
-                        &file.full_path.parent()
-                    } else {
-                        &file.full_path
-                    };
-                    let items = list_files(vfs.as_ref(), &next_path).unwrap_or_else(|err| {
-                        // siv.add_layer(Dialog::info("error"));
-                        sink.send(Box::new(move |siv| {
-                            siv.add_layer(Dialog::info(format!(
-                                "Filed to read folder contexts: {}",
-                                err
-                            )));
-                        }))
-                        .unwrap();
-                        vec![File::new(
-                            "..".to_string(),
-                            file.full_path.clone(),
-                            Kind::Folder,
-                            0,
-                        )]
-                    });
-                    let selected_index = if file.name == ".." {
-                        if let Some(folder) = file.full_path.components().last() {
-                            items.iter().position(|f| f.name.as_str() == *folder)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    };
-                    table.set_items(items);
-                    if let Some(item_index) = selected_index {
-                        table.set_selected_item(item_index);
-                    }
-                }
-                _ => {}
-            }
-        });
+        let file = siv
+            .call_on_name(&table_id, |table: &mut TableView<File, Data>| {
+                table.borrow_item(index).cloned()
+            })
+            .flatten();
+        let (Some(file), Some(loader)) = (
+            file.filter(|file| file.is_folder()),
+            panel_loader(siv, &table_id),
+        ) else {
+            return;
+        };
+        if file.name == ".." {
+            let select = file
+                .full_path
+                .components()
+                .last()
+                .map(|name| name.to_string());
+            loader.load(
+                file.full_path.parent(),
+                select,
+                File::parent_folder(file.full_path),
+            );
+        } else {
+            loader.load(
+                file.full_path.clone(),
+                None,
+                File::parent_folder(file.full_path),
+            );
+        }
     });
-    table.set_items(items);
-    table.set_selected_row(0);
-    table
+    let table_id = id.as_ref().to_string();
+    let table = OnEventView::new(table.with_name(id.as_ref())).on_event('i', move |siv| {
+        let file = siv
+            .call_on_name(&table_id, |table: &mut TableView<File, Data>| {
+                table
+                    .item()
+                    .and_then(|index| table.borrow_item(index).cloned())
+            })
+            .flatten();
+        if let Some(file) = file {
+            show_details(siv, &file);
+        }
+    });
+    Panel::new(table.min_size((30, 20)))
+        .title(title)
+        .with_name(frame_id(id.as_ref()))
+}
+
+/// Shows `path` from `provider` in the panel `id` under `title`, instead of
+/// what it showed before. `plugin` names the plugin that serves `provider`.
+pub fn open_in_panel(
+    siv: &mut Cursive,
+    id: &str,
+    title: impl Into<String>,
+    provider: Arc<dyn AsyncVfs>,
+    plugin: Option<String>,
+    path: AbsolutePath,
+) {
+    let Some(mut loader) = panel_loader(siv, id) else {
+        return;
+    };
+    siv.call_on_name(&frame_id(id), |frame: &mut Frame| frame.set_title(title));
+    loader.provider = provider;
+    loader.plugin = plugin;
+    loader.load(path.clone(), None, File::parent_folder(path));
+    if let Some(panels) = siv.user_data::<Panels>() {
+        panels.0.insert(id.to_string(), loader);
+    }
+}
+
+/// Loads the folders of the panels showing `plugin` again, keeping their
+/// selected entries.
+pub fn refresh_plugin_panels(siv: &mut Cursive, plugin: &str) {
+    let loaders = siv
+        .user_data::<Panels>()
+        .map(|panels| {
+            panels
+                .0
+                .values()
+                .filter(|loader| loader.plugin.as_deref() == Some(plugin))
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    for loader in loaders {
+        refresh(siv, &loader);
+    }
+}
+
+fn refresh(siv: &mut Cursive, loader: &Loader) {
+    let id = &loader.table_id;
+    let select = siv
+        .call_on_name(id, |table: &mut TableView<File, Data>| {
+            table
+                .item()
+                .and_then(|index| table.borrow_item(index))
+                .map(|file| file.name.clone())
+        })
+        .flatten();
+    let path = loader.current.lock().unwrap().clone();
+    loader.load(path.clone(), select, File::parent_folder(path));
 }
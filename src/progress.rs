@@ -0,0 +1,104 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use cursive::{
+    view::{Nameable, Resizable},
+    views::{Dialog, TextView},
+    CbSink, Cursive,
+};
+use tokio::{runtime::Handle, time};
+use vfs::{Operation, Progress};
+
+use crate::secret_prompt::prompt_waiting;
+
+/// Operations that end sooner are not shown.
+const DELAY: Duration = Duration::from_millis(500);
+const REFRESH: Duration = Duration::from_millis(100);
+
+fn format_progress(progress: &Progress) -> String {
+    let count = match progress.total {
+        0 if progress.done == 0 => "waiting for the plugin".to_string(),
+        0 => progress.done.to_string(),
+        total => format!(
+            "{} of {} ({}%)",
+            progress.done,
+            total,
+            progress.done.saturating_mul(100) / total
+        ),
+    };
+    format!("{}\n{}", progress.message, count)
+}
+
+/// Shows the progress of `operation` in a dialog with a Cancel button,
+/// from a moment after it started until it is `done`. `id` names the dialog.
+/// The dialog is hidden while a plugin asks the user for a secret, the
+/// prompt keeps the focus.
+pub fn show_progress(
+    runtime: &Handle,
+    sink: CbSink,
+    id: String,
+    title: String,
+    operation: Arc<Operation>,
+    done: Arc<AtomicBool>,
+) {
+    runtime.spawn(async move {
+        time::sleep(DELAY).await;
+        // the dialog is closed after it was shown, the sink keeps the order
+        let mut shown = false;
+        while !done.load(Ordering::SeqCst) {
+            let visible = !operation.is_cancelled() && !prompt_waiting();
+            if visible != shown {
+                shown = visible;
+                let (id, title, operation) = (id.clone(), title.clone(), operation.clone());
+                let _ = sink.send(Box::new(move |siv| match visible {
+                    true => show(siv, id, title, operation),
+                    false => close(siv, &id),
+                }));
+            }
+            if shown {
+                let progress = format_progress(&operation.progress());
+                let text_id = text_id(&id);
+                let _ = sink.send(Box::new(move |siv| {
+                    siv.call_on_name(&text_id, |text: &mut TextView| text.set_content(progress));
+                }));
+            }
+            time::sleep(REFRESH).await;
+        }
+        if shown {
+            let _ = sink.send(Box::new(move |siv| close(siv, &id)));
+        }
+    });
+}
+
+fn show(siv: &mut Cursive, id: String, title: String, operation: Arc<Operation>) {
+    let dialog = Dialog::around(
+        TextView::new(format_progress(&Progress::default()))
+            .with_name(text_id(&id))
+            .min_width(40),
+    )
+    .title(title)
+    .button("Cancel", {
+        let id = id.clone();
+        move |siv| {
+            operation.cancel();
+            close(siv, &id);
+        }
+    });
+    siv.add_layer(dialog.with_name(id));
+}
+
+fn text_id(id: &str) -> String {
+    format!("{}-text", id)
+}
+
+fn close(siv: &mut Cursive, id: &str) {
+    let screen = siv.screen_mut();
+    if let Some(position) = screen.find_layer_from_name(id) {
+        screen.remove_layer(position);
+    }
+}
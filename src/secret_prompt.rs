@@ -0,0 +1,119 @@
+use std::sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use async_trait::async_trait;
+use cursive::{
+    view::{Nameable, Resizable},
+    views::{Checkbox, Dialog, EditView, LinearLayout, TextView},
+    CbSink, Cursive,
+};
+use tokio::sync::oneshot;
+use wasm::{Secret, SecretPrompt, SecretRequest};
+
+/// Prompts that wait for an answer, progress dialogs stay hidden meanwhile
+/// so that they do not take the focus away from them.
+static WAITING: AtomicUsize = AtomicUsize::new(0);
+/// Names the prompt dialogs, more than one can be open at a time.
+static NEXT_PROMPT: AtomicU64 = AtomicU64::new(0);
+
+pub fn prompt_waiting() -> bool {
+    WAITING.load(Ordering::SeqCst) > 0
+}
+
+/// Counts a prompt in [`WAITING`] until it is answered or given up.
+struct Waiting;
+
+impl Waiting {
+    fn start() -> Waiting {
+        WAITING.fetch_add(1, Ordering::SeqCst);
+        Waiting
+    }
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        WAITING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Asks for the secrets plugins need in a dialog, the plugin waits until
+/// the user answers or cancels.
+pub struct DialogPrompt {
+    sink: CbSink,
+}
+
+impl DialogPrompt {
+    pub fn new(sink: CbSink) -> DialogPrompt {
+        DialogPrompt { sink }
+    }
+}
+
+#[async_trait]
+impl SecretPrompt for DialogPrompt {
+    async fn prompt_secret(&self, request: SecretRequest) -> Option<Secret> {
+        let (sender, receiver) = oneshot::channel();
+        let _waiting = Waiting::start();
+        self.sink
+            .send(Box::new(move |siv| show_prompt(siv, request, sender)))
+            .ok()?;
+        // a dialog that is closed in any other way drops the sender
+        receiver.await.ok().flatten()
+    }
+}
+
+fn show_prompt(siv: &mut Cursive, request: SecretRequest, sender: oneshot::Sender<Option<Secret>>) {
+    let id = format!(
+        "secret-prompt-{}",
+        NEXT_PROMPT.fetch_add(1, Ordering::SeqCst)
+    );
+    let (secret_id, remember_id) = (format!("{}-secret", id), format!("{}-remember", id));
+    // both buttons answer, only the first one to be pressed is sent
+    let sender = Arc::new(Mutex::new(Some(sender)));
+    let dialog_id = id.clone();
+    let answer = move |siv: &mut Cursive, secret: Option<Secret>| {
+        // other dialogs may have been opened on top of the prompt since
+        let screen = siv.screen_mut();
+        if let Some(position) = screen.find_layer_from_name(&dialog_id) {
+            screen.remove_layer(position);
+        }
+        if let Some(sender) = sender.lock().unwrap().take() {
+            let _ = sender.send(secret);
+        }
+    };
+    let cancel = answer.clone();
+    let dialog = Dialog::around(
+        LinearLayout::vertical()
+            .child(TextView::new(format!(
+                "{} on {} asks:\n{}",
+                request.plugin, request.uri, request.description
+            )))
+            .child(
+                EditView::new()
+                    .secret()
+                    .with_name(secret_id.clone())
+                    .fixed_width(40),
+            )
+            .child(
+                LinearLayout::horizontal()
+                    .child(Checkbox::new().with_name(remember_id.clone()))
+                    .child(TextView::new(" Remember until unmounted")),
+            ),
+    )
+    .title(request.title)
+    .button("Ok", move |siv| {
+        let value = siv
+            .call_on_name(&secret_id, |edit: &mut EditView| edit.get_content())
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        let remember = siv
+            .call_on_name(&remember_id, |checkbox: &mut Checkbox| {
+                checkbox.is_checked()
+            })
+            .unwrap_or(false);
+        answer(siv, Some(Secret { value, remember }));
+    })
+    .button("Cancel", move |siv| cancel(siv, None));
+    siv.add_layer(dialog.with_name(id));
+}